@@ -11,6 +11,90 @@ static DB: Lazy<Mutex<Result<DbManager>>> = Lazy::new(|| {
     Mutex::new(DbManager::new())
 });
 
+// une table (et un classement) par jeu ou variante de jeu
+pub const SCORE_TABLES: &[&str] = &[
+    "guessing_easy",
+    "guessing",
+    "guessing_hard",
+    "guessing_expert",
+    "guessing_custom",
+    "guessing_timed",
+    "motus",
+    "reflex",
+    "hangman",
+    "mastermind",
+    "typing",
+    "simon_slow",
+    "simon_normal",
+    "simon_fast",
+    "minesweeper_beginner",
+    "minesweeper_intermediate",
+    "minesweeper_expert",
+    "game_2048",
+    "sudoku_easy",
+    "sudoku_medium",
+    "sudoku_hard",
+];
+
+// tables dont le score est un temps : le plus petit est le meilleur
+const LOWER_IS_BETTER: &[&str] = &[
+    "minesweeper_beginner",
+    "minesweeper_intermediate",
+    "minesweeper_expert",
+    "sudoku_easy",
+    "sudoku_medium",
+    "sudoku_hard",
+];
+
+fn score_order(table_name: &str) -> &'static str {
+    if LOWER_IS_BETTER.contains(&table_name) {
+        "ASC"
+    } else {
+        "DESC"
+    }
+}
+
+// jeux classes par un rating (victoire/nul/defaite) plutot que par un score
+pub const RATING_TABLES: &[&str] = &[
+    "tictactoe",
+];
+pub const INITIAL_RATING: i32 = 1000;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Win => "win",
+            Outcome::Draw => "draw",
+            Outcome::Loss => "loss",
+        }
+    }
+
+    // resultat du point de vue du joueur, pour le calcul du rating
+    pub fn points(&self) -> f64 {
+        match self {
+            Outcome::Win => 1.0,
+            Outcome::Draw => 0.5,
+            Outcome::Loss => 0.0,
+        }
+    }
+}
+
+pub struct MotusHistoryEntry {
+    pub id: i64,
+    pub theme: String,
+    pub attempts_used: i32,
+    pub won: bool,
+    pub grid: String,
+    pub played_at: String,
+}
+
 pub struct DbManager {
     conn: Connection,
 }
@@ -31,34 +115,112 @@ impl DbManager {
             [],
         )?;
 
+        for table_name in SCORE_TABLES {
+            self.conn.execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {} (
+                        id INTEGER PRIMARY KEY,
+                        user_id INTEGER,
+                        username TEXT,
+                        score INTEGER,
+                        FOREIGN KEY (user_id) REFERENCES users(id)
+                    )",
+                    table_name
+                ),
+                [],
+            )?;
+        }
+
+        for table_name in RATING_TABLES {
+            self.conn.execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {} (
+                        id INTEGER PRIMARY KEY,
+                        user_id INTEGER,
+                        username TEXT,
+                        opponent TEXT,
+                        outcome TEXT,
+                        rating INTEGER,
+                        played_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                        FOREIGN KEY (user_id) REFERENCES users(id)
+                    )",
+                    table_name
+                ),
+                [],
+            )?;
+        }
+
         self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS guessing (
+            "CREATE TABLE IF NOT EXISTS guessing_reverse (
                 id INTEGER PRIMARY KEY,
                 user_id INTEGER,
                 username TEXT,
-                score INTEGER,
+                strategy TEXT,
+                attempts INTEGER,
                 FOREIGN KEY (user_id) REFERENCES users(id)
             )",
             [],
         )?;
 
         self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS motus (
+            "CREATE TABLE IF NOT EXISTS reflex_sessions (
                 id INTEGER PRIMARY KEY,
                 user_id INTEGER,
-                username TEXT,
                 score INTEGER,
+                stats TEXT,
+                played_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 FOREIGN KEY (user_id) REFERENCES users(id)
             )",
             [],
         )?;
 
         self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS reflex (
+            "CREATE TABLE IF NOT EXISTS typing_results (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER,
+                language TEXT,
+                wpm REAL,
+                raw_wpm REAL,
+                accuracy REAL,
+                played_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (user_id) REFERENCES users(id)
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS game_2048_results (
                 id INTEGER PRIMARY KEY,
                 user_id INTEGER,
-                username TEXT,
                 score INTEGER,
+                highest_tile INTEGER,
+                undo_used BOOLEAN,
+                played_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (user_id) REFERENCES users(id)
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_settings (
+                user_id INTEGER,
+                key TEXT,
+                value TEXT,
+                PRIMARY KEY (user_id, key),
+                FOREIGN KEY (user_id) REFERENCES users(id)
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS motus_history (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER,
+                theme TEXT,
+                attempts_used INTEGER,
+                won INTEGER,
+                grid TEXT,
+                played_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 FOREIGN KEY (user_id) REFERENCES users(id)
             )",
             [],
@@ -102,8 +264,9 @@ impl DbManager {
 
     fn p_get_leaderboard(&self, table_name: &str) -> Result<Vec<(String, i32)>> {
         let query = format!(
-            "SELECT username, score FROM {} ORDER BY score DESC LIMIT 5",
-            table_name
+            "SELECT username, score FROM {} ORDER BY score {} LIMIT 5",
+            table_name,
+            score_order(table_name)
         );
         
         let mut stmt = self.conn.prepare(&query)?;
@@ -118,13 +281,13 @@ impl DbManager {
     }
 
     fn p_get_user_best_scores(&self, user_id: i64) -> Result<Vec<(String, Option<i32>)>> {
-        let games = vec!["guessing", "motus", "reflex"];
         let mut results = Vec::new();
         
-        for game in games {
+        for game in SCORE_TABLES {
             let query = format!(
-                "SELECT score FROM {} WHERE user_id = ?1 ORDER BY score DESC LIMIT 1",
-                game
+                "SELECT score FROM {} WHERE user_id = ?1 ORDER BY score {} LIMIT 1",
+                game,
+                score_order(game)
             );
             
             let mut stmt = self.conn.prepare(&query)?;
@@ -143,6 +306,168 @@ impl DbManager {
         Ok(results)
     }
 
+    fn p_add_game_outcome(&self, table_name: &str, user_id: i64, username: &str, opponent: &str, outcome: Outcome, rating: i32) -> Result<()> {
+        let query = format!(
+            "INSERT INTO {} (user_id, username, opponent, outcome, rating) VALUES (?1, ?2, ?3, ?4, ?5)",
+            table_name
+        );
+
+        self.conn.execute(&query, rusqlite::params![user_id, username, opponent, outcome.as_str(), rating])?;
+
+        Ok(())
+    }
+
+    // le rating courant est celui de la derniere partie jouee
+    fn p_get_user_rating(&self, table_name: &str, user_id: i64) -> Result<Option<i32>> {
+        let query = format!(
+            "SELECT rating FROM {} WHERE user_id = ?1 ORDER BY id DESC LIMIT 1",
+            table_name
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let mut rows = stmt.query([user_id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // (victoires, nuls, defaites)
+    fn p_get_user_outcomes(&self, table_name: &str, user_id: i64) -> Result<(i32, i32, i32)> {
+        let query = format!(
+            "SELECT
+                COALESCE(SUM(outcome = 'win'), 0),
+                COALESCE(SUM(outcome = 'draw'), 0),
+                COALESCE(SUM(outcome = 'loss'), 0)
+            FROM {} WHERE user_id = ?1",
+            table_name
+        );
+
+        self.conn.query_row(&query, [user_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+    }
+
+    fn p_get_rating_leaderboard(&self, table_name: &str) -> Result<Vec<(String, i32)>> {
+        let query = format!(
+            "SELECT username, rating FROM {0}
+            WHERE id IN (SELECT MAX(id) FROM {0} GROUP BY user_id)
+            ORDER BY rating DESC LIMIT 5",
+            table_name
+        );
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let results = stmt.query_map([], |row| {
+            let username: String = row.get(0)?;
+            let rating: i32 = row.get(1)?;
+            Ok((username, rating))
+        })?
+        .collect::<Result<Vec<(String, i32)>>>()?;
+
+        Ok(results)
+    }
+
+    fn p_add_motus_history(&self, user_id: i64, theme: &str, attempts_used: i32, won: bool, grid: &str) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO motus_history (user_id, theme, attempts_used, won, grid) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![user_id, theme, attempts_used, won, grid],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    fn p_get_motus_history(&self, user_id: i64) -> Result<Vec<MotusHistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, theme, attempts_used, won, grid, played_at FROM motus_history
+             WHERE user_id = ?1 ORDER BY id DESC LIMIT 10",
+        )?;
+        let results = stmt.query_map([user_id], |row| {
+            Ok(MotusHistoryEntry {
+                id: row.get(0)?,
+                theme: row.get(1)?,
+                attempts_used: row.get(2)?,
+                won: row.get(3)?,
+                grid: row.get(4)?,
+                played_at: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<MotusHistoryEntry>>>()?;
+
+        Ok(results)
+    }
+
+    fn p_add_reverse_guessing(&self, user_id: i64, username: &str, strategy: &str, attempts: i32) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO guessing_reverse (user_id, username, strategy, attempts) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![user_id, username, strategy, attempts],
+        )?;
+
+        Ok(())
+    }
+
+    fn p_get_reverse_guessing_average(&self, user_id: i64, strategy: &str) -> Result<Option<f64>> {
+        self.conn.query_row(
+            "SELECT AVG(attempts) FROM guessing_reverse WHERE user_id = ?1 AND strategy = ?2",
+            rusqlite::params![user_id, strategy],
+            |row| row.get(0),
+        )
+    }
+
+    fn p_add_reflex_session(&self, user_id: i64, score: i32, stats: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO reflex_sessions (user_id, score, stats) VALUES (?1, ?2, ?3)",
+            rusqlite::params![user_id, score, stats],
+        )?;
+
+        Ok(())
+    }
+
+    fn p_add_typing_result(&self, user_id: i64, language: &str, wpm: f64, raw_wpm: f64, accuracy: f64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO typing_results (user_id, language, wpm, raw_wpm, accuracy) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![user_id, language, wpm, raw_wpm, accuracy],
+        )?;
+
+        Ok(())
+    }
+
+    fn p_get_typing_personal_best(&self, user_id: i64, language: &str) -> Result<Option<f64>> {
+        self.conn.query_row(
+            "SELECT MAX(wpm) FROM typing_results WHERE user_id = ?1 AND language = ?2",
+            rusqlite::params![user_id, language],
+            |row| row.get(0),
+        )
+    }
+
+    fn p_add_2048_result(&self, user_id: i64, score: i32, highest_tile: i32, undo_used: bool) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO game_2048_results (user_id, score, highest_tile, undo_used) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![user_id, score, highest_tile, undo_used],
+        )?;
+
+        Ok(())
+    }
+
+    fn p_get_user_setting(&self, user_id: i64, key: &str) -> Result<Option<String>> {
+        let mut stmt = self.conn.prepare("SELECT value FROM user_settings WHERE user_id = ?1 AND key = ?2")?;
+        let mut rows = stmt.query(rusqlite::params![user_id, key])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn p_set_user_setting(&self, user_id: i64, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO user_settings (user_id, key, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT(user_id, key) DO UPDATE SET value = excluded.value",
+            rusqlite::params![user_id, key, value],
+        )?;
+
+        Ok(())
+    }
 
     // API publique statique
     pub fn init() -> Result<()> {
@@ -193,4 +518,116 @@ impl DbManager {
         }
     }
 
+    pub fn add_motus_history(user_id: i64, theme: &str, attempts_used: i32, won: bool, grid: &str) -> Result<i64> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_add_motus_history(user_id, theme, attempts_used, won, grid),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_motus_history(user_id: i64) -> Result<Vec<MotusHistoryEntry>> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_motus_history(user_id),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn add_reverse_guessing(user_id: i64, username: &str, strategy: &str, attempts: i32) -> Result<()> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_add_reverse_guessing(user_id, username, strategy, attempts),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_reverse_guessing_average(user_id: i64, strategy: &str) -> Result<Option<f64>> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_reverse_guessing_average(user_id, strategy),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn add_reflex_session(user_id: i64, score: i32, stats: &str) -> Result<()> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_add_reflex_session(user_id, score, stats),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_user_setting(user_id: i64, key: &str) -> Result<Option<String>> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_user_setting(user_id, key),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn set_user_setting(user_id: i64, key: &str, value: &str) -> Result<()> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_set_user_setting(user_id, key, value),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn add_typing_result(user_id: i64, language: &str, wpm: f64, raw_wpm: f64, accuracy: f64) -> Result<()> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_add_typing_result(user_id, language, wpm, raw_wpm, accuracy),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_typing_personal_best(user_id: i64, language: &str) -> Result<Option<f64>> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_typing_personal_best(user_id, language),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn add_game_outcome(table_name: &str, user_id: i64, username: &str, opponent: &str, outcome: Outcome, rating: i32) -> Result<()> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_add_game_outcome(table_name, user_id, username, opponent, outcome, rating),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_user_rating(table_name: &str, user_id: i64) -> Result<Option<i32>> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_user_rating(table_name, user_id),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_user_outcomes(table_name: &str, user_id: i64) -> Result<(i32, i32, i32)> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_user_outcomes(table_name, user_id),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn get_rating_leaderboard(table_name: &str) -> Result<Vec<(String, i32)>> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_get_rating_leaderboard(table_name),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
+    pub fn add_2048_result(user_id: i64, score: i32, highest_tile: i32, undo_used: bool) -> Result<()> {
+        let db_guard = DB.lock().unwrap();
+        match &*db_guard {
+            Ok(manager) => manager.p_add_2048_result(user_id, score, highest_tile, undo_used),
+            Err(_) => Err(Error::ExecuteReturnedResults),
+        }
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,268 @@
+use std::error::Error;
+use std::io::Write;
+use rand::Rng;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::utils;
+use crate::input::{self, Key, RawMode};
+
+pub const GAME_NAME: &str = "game_2048";
+const SIZE: usize = 4;
+const WINNING_TILE: u32 = 2048;
+
+type Grid = [[u32; SIZE]; SIZE];
+
+#[derive(Clone, Copy)]
+enum Move {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Move {
+    fn from_key(key: Key) -> Option<Self> {
+        match key {
+            // WASD (QWERTY) et ZQSD (AZERTY)
+            Key::Left | Key::Char('a') | Key::Char('q') => Some(Move::Left),
+            Key::Right | Key::Char('d') => Some(Move::Right),
+            Key::Up | Key::Char('w') | Key::Char('z') => Some(Move::Up),
+            Key::Down | Key::Char('s') => Some(Move::Down),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum GameState {
+    InProgress,
+    Over,
+    Quit,
+}
+
+struct Game {
+    grid: Grid,
+    score: u32,
+    // un seul niveau d'annulation : la grille et le score avant le dernier coup
+    previous: Option<(Grid, u32)>,
+    undo_used: bool,
+    state: GameState,
+}
+
+impl Game {
+    fn new() -> Self {
+        let mut game = Game {
+            grid: [[0; SIZE]; SIZE],
+            score: 0,
+            previous: None,
+            undo_used: false,
+            state: GameState::InProgress,
+        };
+        game.spawn_tile();
+        game.spawn_tile();
+        game
+    }
+
+    // une nouvelle tuile (2 a 90%, sinon 4) sur une case vide au hasard
+    fn spawn_tile(&mut self) {
+        let empty: Vec<(usize, usize)> = (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.grid[row][col] == 0)
+            .collect();
+        if empty.is_empty() {
+            return;
+        }
+
+        let mut rng = rand::rng();
+        let (row, col) = empty[rng.random_range(0..empty.len())];
+        self.grid[row][col] = if rng.random_bool(0.9) { 2 } else { 4 };
+    }
+
+    fn play_move(&mut self, direction: Move) -> bool {
+        let (grid, gained) = slide_grid(&self.grid, direction);
+        if grid == self.grid {
+            return false;
+        }
+
+        self.previous = Some((self.grid, self.score));
+        self.grid = grid;
+        self.score += gained;
+        self.spawn_tile();
+        if !can_move(&self.grid) {
+            self.state = GameState::Over;
+        }
+        true
+    }
+
+    fn undo(&mut self) -> bool {
+        match self.previous.take() {
+            Some((grid, score)) => {
+                self.grid = grid;
+                self.score = score;
+                self.undo_used = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn highest_tile(&self) -> u32 {
+        self.grid.iter().flatten().copied().max().unwrap_or(0)
+    }
+}
+
+// pousse une ligne vers la gauche, chaque tuile ne fusionne qu'une fois par coup
+fn slide_line(line: [u32; SIZE]) -> ([u32; SIZE], u32) {
+    let tiles: Vec<u32> = line.into_iter().filter(|&tile| tile != 0).collect();
+    let mut result = [0; SIZE];
+    let mut gained = 0;
+    let mut position = 0;
+    let mut i = 0;
+    while i < tiles.len() {
+        if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] {
+            result[position] = tiles[i] * 2;
+            gained += tiles[i] * 2;
+            i += 2;
+        } else {
+            result[position] = tiles[i];
+            i += 1;
+        }
+        position += 1;
+    }
+    (result, gained)
+}
+
+fn slide_grid(grid: &Grid, direction: Move) -> (Grid, u32) {
+    let mut result = [[0; SIZE]; SIZE];
+    let mut gained = 0;
+    for index in 0..SIZE {
+        // chaque ligne (ou colonne) est lue dans le sens du mouvement
+        let cells: [(usize, usize); SIZE] = std::array::from_fn(|k| match direction {
+            Move::Left => (index, k),
+            Move::Right => (index, SIZE - 1 - k),
+            Move::Up => (k, index),
+            Move::Down => (SIZE - 1 - k, index),
+        });
+        let (line, line_gain) = slide_line(cells.map(|(row, col)| grid[row][col]));
+        for (k, (row, col)) in cells.into_iter().enumerate() {
+            result[row][col] = line[k];
+        }
+        gained += line_gain;
+    }
+    (result, gained)
+}
+
+fn can_move(grid: &Grid) -> bool {
+    (0..SIZE).any(|row| {
+        (0..SIZE).any(|col| {
+            grid[row][col] == 0
+                || (col + 1 < SIZE && grid[row][col] == grid[row][col + 1])
+                || (row + 1 < SIZE && grid[row][col] == grid[row + 1][col])
+        })
+    })
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    loop {
+        let game = play_round()?;
+
+        println!("Final score: {}", game.score);
+        println!("Highest tile: {}", game.highest_tile());
+        match DB::add_2048_result(user_id, game.score as i32, game.highest_tile() as i32, game.undo_used) {
+            Ok(_) => {},
+            Err(e) => println!("Failed to save result: {}", e),
+        }
+        if game.undo_used {
+            println!("You used undo, this game does not count for the leaderboard.");
+        } else {
+            match DB::add_game_score(GAME_NAME, user_id, &username, game.score as i32) {
+                Ok(_) => {},
+                Err(e) => println!("Failed to save score: {}", e),
+            }
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn play_round() -> Result<Game, Box<dyn Error>> {
+    let _raw_mode = RawMode::enable()?;
+    let mut game = Game::new();
+    let mut message = String::new();
+    let mut reached_winning_tile = false;
+
+    while game.state == GameState::InProgress {
+        display_game(&game, &message)?;
+        message.clear();
+
+        let (key, _) = input::read_key()?;
+        match key {
+            Key::Char('u') => {
+                if !game.undo() {
+                    message = "Nothing to undo.".to_string();
+                }
+            }
+            Key::Esc => {
+                display_game(&game, "Quit this game? Press 'y' to confirm, any other key to continue.")?;
+                if input::read_key()?.0 == Key::Char('y') {
+                    game.state = GameState::Quit;
+                }
+            }
+            _ => match Move::from_key(key) {
+                Some(direction) => {
+                    game.play_move(direction);
+                }
+                None => message = "Use the arrows, WASD or ZQSD to move, 'u' to undo, Esc to quit.".to_string(),
+            },
+        }
+
+        if !reached_winning_tile && game.highest_tile() >= WINNING_TILE {
+            reached_winning_tile = true;
+            message = format!("You reached {}! Keep going for a higher score.", WINNING_TILE);
+        }
+    }
+
+    display_game(&game, "")?;
+    match game.state {
+        GameState::Over => print!("No more moves, game over!\r\n"),
+        _ => print!("Game stopped.\r\n"),
+    }
+    Ok(game)
+}
+
+fn display_game(game: &Game, message: &str) -> Result<(), Box<dyn Error>> {
+    utils::clear_terminal();
+    print!("2048 - Score: {}{}\r\n\r\n", game.score, if game.undo_used { " (undo used)" } else { "" });
+
+    let separator = format!("+{}", "------+".repeat(SIZE));
+    print!("{}\r\n", separator);
+    for row in &game.grid {
+        let cells: String = row
+            .iter()
+            .map(|&tile| match tile {
+                0 => "      |".to_string(),
+                tile => format!("{:^6}|", tile),
+            })
+            .collect();
+        print!("|{}\r\n{}\r\n", cells, separator);
+    }
+
+    print!("\r\nArrows/WASD/ZQSD: move  u: undo (forfeits the leaderboard)  Esc: quit\r\n");
+    if !message.is_empty() {
+        print!("{}\r\n", message);
+    }
+    std::io::stdout().flush()?;
+    Ok(())
+}
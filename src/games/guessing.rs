@@ -1,59 +1,182 @@
 use rand::Rng;
 use std::cmp::Ordering;
 use std::error::Error;
+use std::time::{Duration, Instant};
 
-use crate::{utils, User};
+use crate::{input, utils, User};
 use crate::db::DbManager as DB;
 
 pub const GAME_NAME: &str = "guessing";
+// essais accordes en plus du minimum theorique (recherche dichotomique)
+const EXTRA_TRIALS: i32 = 3;
+const OUT_OF_INTERVAL_PENALTY: i32 = 5;
+const TIMED_GAME_NAME: &str = "guessing_timed";
+const TIME_ATTACK_SECONDS: u64 = 60;
+const POINTS_PER_NUMBER: i32 = 100;
+
+#[derive(Clone, Copy)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Expert,
+    Custom { min: u32, max: u32 },
+}
+
+impl Difficulty {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(Difficulty::Easy),
+            2 => Some(Difficulty::Normal),
+            3 => Some(Difficulty::Hard),
+            4 => Some(Difficulty::Expert),
+            5 => select_custom_range(),
+            _ => None,
+        }
+    }
+
+    fn range(&self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (1, 50),
+            Difficulty::Normal => (1, 100),
+            Difficulty::Hard => (1, 1_000),
+            Difficulty::Expert => (1, 1_000_000),
+            Difficulty::Custom { min, max } => (*min, *max),
+        }
+    }
+
+    // log2 de la taille de l'intervalle, arrondi au superieur
+    fn max_trials(&self) -> i32 {
+        let (min, max) = self.range();
+        let size = (max - min) as f64 + 1.0;
+        size.log2().ceil() as i32 + EXTRA_TRIALS
+    }
+
+    fn table_name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "guessing_easy",
+            Difficulty::Normal => GAME_NAME,
+            Difficulty::Hard => "guessing_hard",
+            Difficulty::Expert => "guessing_expert",
+            Difficulty::Custom { .. } => "guessing_custom",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Strategy {
+    BinarySearch,
+    Random,
+    HumanLike,
+}
+
+impl Strategy {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(Strategy::BinarySearch),
+            2 => Some(Strategy::Random),
+            3 => Some(Strategy::HumanLike),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Strategy::BinarySearch => "binary search",
+            Strategy::Random => "random",
+            Strategy::HumanLike => "human-like",
+        }
+    }
+
+    fn next_guess(&self, low: u32, high: u32) -> u32 {
+        let mut rng = rand::rng();
+        match self {
+            Strategy::BinarySearch => low + (high - low) / 2,
+            Strategy::Random => rng.random_range(low..=high),
+            Strategy::HumanLike => {
+                // vise a peu pres le milieu puis arrondit a un nombre "rond"
+                let size = high - low;
+                let jitter = size / 6;
+                let middle = low + size / 2;
+                let guess = rng.random_range(middle - jitter..=middle + jitter);
+                let step = if size >= 100 { 10 } else if size >= 20 { 5 } else { 1 };
+                (guess / step * step).clamp(low, high)
+            }
+        }
+    }
+}
 
 pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+  println!("Welcome on the Guessing Game !\n");
+
+  println!("Select a mode:");
+  println!("1. Classic");
+  println!("2. Reverse (the computer guesses your number)");
+  println!("3. Time attack ({} seconds, 1-100)", TIME_ATTACK_SECONDS);
+  let mode = utils::get_valid_choice();
+  if mode == 3 {
+      return play_timed(user);
+  }
+
+  let difficulty = match select_difficulty() {
+      Some(difficulty) => difficulty,
+      None => {
+          println!("Invalid choice. Exiting game.");
+          return Ok(());
+      }
+  };
+
+  match mode {
+      2 => play_reverse(user, difficulty),
+      _ => play_classic(user, difficulty),
+  }
+}
+
+fn play_classic(user: &User, difficulty: Difficulty) -> Result<(), Box<dyn Error>> {
   let user_id = user.id;
   let username = user.username.clone();
-  println!("Welcome on the Guessing Game !\n");
+  let (min, max) = difficulty.range();
 
     loop {
         utils::clear_terminal();
-        let mut remaining_trials: i32 = 10;
-        let mut numbers_tested: Vec<u32> = Vec::new();
-        let secret_number: u32 = generate_random_number();
-        println!("You have {} trials to guess the number between 1 and 100.", remaining_trials);
-        println!("Once you exceed 10 attempts, your score will be 0.");
-        // println!("The number is: {}", secret_number);
-        loop {
-            println!("Please enter your guess (1-100):");
+        let mut round = Round::new(min, max, difficulty.max_trials());
+        println!("You have {} trials to guess the number between {} and {}.", round.remaining_trials, min, max);
+        println!("If you have not found it after {} attempts, you lose and your score will be 0.", round.remaining_trials);
+        // println!("The number is: {}", round.secret_number);
+        while round.state == RoundState::Playing {
+            println!("Please enter your guess ({}-{}):", min, max);
             let input: u32 = get_valid_number();
-            if !(1..=100).contains(&input) {
-                println!("Invalid input. Please enter a number between 1 and 100.");
+
+            if round.numbers_tested.contains(&input) {
+                println!("You already tried {}! This guess does not count.", input);
                 continue;
             }
+            if !(min..=max).contains(&input) {
+                println!("{} is not between {} and {}: this costs a trial and {} points.", input, min, max, OUT_OF_INTERVAL_PENALTY);
+            } else if !round.is_still_possible(input) {
+                println!("This guess is outside the still-possible range ({}-{})! (-{} points)", round.low, round.high, OUT_OF_INTERVAL_PENALTY);
+            }
 
-            match input.cmp(&secret_number) {
-                Ordering::Less => {
-                    println!("Your guess is too low!");
-                }
-                Ordering::Greater => {
-                    println!("Your guess is too high!");
-                }
-                Ordering::Equal => {
-                    println!("Congratulations {}! You guessed the number!", username);
-                    break;
+            match round.submit_guess(input) {
+                Ordering::Less => println!("Your guess is too low!"),
+                Ordering::Greater => println!("Your guess is too high!"),
+                Ordering::Equal => println!("Congratulations {}! You guessed the number!", username),
+            }
+
+            match round.state {
+                RoundState::Playing => {
+                    println!("You have {} trials left.", round.remaining_trials);
+                    display_number_tested(&round.numbers_tested);
                 }
+                RoundState::Lost => println!("No trials left! The number was {}.", round.secret_number),
+                RoundState::Won => {}
             }
-            
-            remaining_trials -= 1;
-            numbers_tested.push(input);
-            display_number_tested(&numbers_tested);
         }
         
-        let score: i32 = if remaining_trials > 0 {
-            remaining_trials * 10
-        } else {
-            0
-        };
-        
+        let score = round.score();
+        display_analysis(&round.analysis);
         println!("Your score is: {}", score);
-        match DB::add_game_score(GAME_NAME, user_id, &username, score) {
+        match DB::add_game_score(difficulty.table_name(), user_id, &username, score) {
             Ok(_) => {}
             Err(e) => println!("Failed to save score: {}", e),
         }
@@ -70,9 +193,320 @@ pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn generate_random_number() -> u32 {
+fn play_reverse(user: &User, difficulty: Difficulty) -> Result<(), Box<dyn Error>> {
+    let (min, max) = difficulty.range();
+
+    println!("Select the computer's strategy:");
+    println!("1. Binary search");
+    println!("2. Random within bounds");
+    println!("3. Human-like");
+    let strategy = match Strategy::from_choice(utils::get_valid_choice()) {
+        Some(strategy) => strategy,
+        None => {
+            println!("Invalid choice. Exiting game.");
+            return Ok(());
+        }
+    };
+
+    loop {
+        utils::clear_terminal();
+        println!("Think of a number between {} and {}, the computer will try to guess it.", min, max);
+        println!("Answer 'h' if your number is higher, 'l' if it is lower, 'c' if the guess is correct.");
+
+        let mut answers: Vec<(u32, Ordering)> = Vec::new();
+        let (mut low, mut high) = (min, max);
+        let consistent = loop {
+            if low > high {
+                break false;
+            }
+            let guess = strategy.next_guess(low, high);
+            let answer = get_valid_answer(&format!("Is it {}? (h/l/c)", guess));
+            answers.push((guess, answer));
+            match answer {
+                // le nombre du joueur est plus grand que la proposition
+                Ordering::Greater => low = guess.saturating_add(1),
+                Ordering::Less if guess == 0 => break false,
+                Ordering::Less => high = guess - 1,
+                Ordering::Equal => break true,
+            }
+        };
+
+        if !consistent {
+            println!("Your answers are inconsistent: no number between {} and {} fits them. Cheater!", min, max);
+        } else {
+            let attempts = answers.len() as i32;
+            println!("The computer found your number in {} attempts!", attempts);
+
+            println!("What was your number? (to check your answers)");
+            let number = get_valid_number();
+            let honest = (min..=max).contains(&number)
+                && answers.iter().all(|&(guess, answer)| number.cmp(&guess) == answer);
+            if honest {
+                match DB::add_reverse_guessing(user.id, &user.username, strategy.as_str(), attempts) {
+                    Ok(_) => {}
+                    Err(e) => println!("Failed to save the computer's attempts: {}", e),
+                }
+                if let Ok(Some(average)) = DB::get_reverse_guessing_average(user.id, strategy.as_str()) {
+                    println!("On average, the {} strategy needs {:.1} attempts against you.", strategy.as_str(), average);
+                }
+            } else {
+                println!("{} does not match your answers. Cheater! This game is not recorded.", number);
+            }
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// autant de nombres que possible avant la fin du chrono ; rater un nombre
+// (plus d'essais) termine la serie
+fn play_timed(user: &User) -> Result<(), Box<dyn Error>> {
+    let difficulty = Difficulty::Normal;
+    let (min, max) = difficulty.range();
+
+    loop {
+        utils::clear_terminal();
+        println!("Find as many numbers between {} and {} as you can in {} seconds!", min, max, TIME_ATTACK_SECONDS);
+        println!("Each number found earns {} points plus the seconds left on the clock.", POINTS_PER_NUMBER);
+        println!("You have {} trials per number: missing one ends the run.", difficulty.max_trials());
+        utils::get_valid_input("Type 'go' to start:");
+
+        let deadline = Instant::now() + Duration::from_secs(TIME_ATTACK_SECONDS);
+        let mut numbers_found = 0;
+        let mut time_bonus = 0;
+
+        'run: loop {
+            let mut round = Round::new(min, max, difficulty.max_trials());
+            println!("\nNumber #{}", numbers_found + 1);
+
+            while round.state == RoundState::Playing {
+                let prompt = |remaining: Duration| format!("[{:>2.0}s] Your guess ({}-{}): ", remaining.as_secs_f64().ceil(), min, max);
+                let input = match input::read_line_until(deadline, prompt)? {
+                    Some(input) => input,
+                    None => {
+                        println!("Time's up! The number was {}.", round.secret_number);
+                        break 'run;
+                    }
+                };
+                let guess: u32 = match input.trim().parse() {
+                    Ok(guess) => guess,
+                    Err(_) => {
+                        println!("Invalid input. Please enter a valid number.");
+                        continue;
+                    }
+                };
+                if round.numbers_tested.contains(&guess) {
+                    println!("You already tried {}!", guess);
+                    continue;
+                }
+
+                match round.submit_guess(guess) {
+                    Ordering::Less => println!("Too low!"),
+                    Ordering::Greater => println!("Too high!"),
+                    Ordering::Equal => {
+                        let seconds_left = deadline.saturating_duration_since(Instant::now()).as_secs() as i32;
+                        numbers_found += 1;
+                        time_bonus += seconds_left;
+                        println!("Found! +{} points, +{} time bonus.", POINTS_PER_NUMBER, seconds_left);
+                    }
+                }
+            }
+
+            if round.state == RoundState::Lost {
+                println!("No trials left! The number was {}. Your run ends here.", round.secret_number);
+                break;
+            }
+        }
+
+        let score = numbers_found * POINTS_PER_NUMBER + time_bonus;
+        println!("\nNumbers found: {}", numbers_found);
+        println!("Time bonus: {}", time_bonus);
+        println!("Your score is: {}", score);
+        match DB::add_game_score(TIMED_GAME_NAME, user.id, &user.username, score) {
+            Ok(_) => {}
+            Err(e) => println!("Failed to save score: {}", e),
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// renvoie la position du nombre du joueur par rapport a la proposition
+fn get_valid_answer(prompt: &str) -> Ordering {
+    loop {
+        let input = utils::get_valid_input(prompt);
+        match input.to_lowercase().as_str() {
+            "h" => return Ordering::Greater,
+            "l" => return Ordering::Less,
+            "c" => return Ordering::Equal,
+            _ => println!("Invalid input. Please enter 'h', 'l' or 'c':"),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum RoundState {
+    Playing,
+    Won,
+    Lost,
+}
+
+struct Round {
+    secret_number: u32,
+    remaining_trials: i32,
+    numbers_tested: Vec<u32>,
+    analysis: Vec<GuessAnalysis>,
+    // intervalle encore possible d'apres les indices deja donnes
+    low: u32,
+    high: u32,
+    state: RoundState,
+}
+
+impl Round {
+    fn new(min: u32, max: u32, max_trials: i32) -> Self {
+        Round {
+            secret_number: generate_random_number(min, max),
+            remaining_trials: max_trials,
+            numbers_tested: Vec::new(),
+            analysis: Vec::new(),
+            low: min,
+            high: max,
+            state: RoundState::Playing,
+        }
+    }
+
+    fn is_still_possible(&self, guess: u32) -> bool {
+        (self.low..=self.high).contains(&guess)
+    }
+
+    fn submit_guess(&mut self, guess: u32) -> Ordering {
+        self.analysis.push(GuessAnalysis::new(guess, self.low, self.high));
+        self.numbers_tested.push(guess);
+
+        let ordering = guess.cmp(&self.secret_number);
+        match ordering {
+            Ordering::Less => self.low = self.low.max(guess + 1),
+            Ordering::Greater => self.high = self.high.min(guess - 1),
+            Ordering::Equal => {
+                self.state = RoundState::Won;
+                return ordering;
+            }
+        }
+
+        self.remaining_trials -= 1;
+        if self.remaining_trials == 0 {
+            self.state = RoundState::Lost;
+        }
+        ordering
+    }
+
+    fn score(&self) -> i32 {
+        if self.state != RoundState::Won {
+            return 0;
+        }
+        let penalty = self.analysis.iter().filter(|guess| !guess.in_interval).count() as i32 * OUT_OF_INTERVAL_PENALTY;
+        (self.remaining_trials * 10 - penalty).max(0)
+    }
+}
+
+// compare chaque essai au choix optimal (le milieu de l'intervalle encore possible)
+struct GuessAnalysis {
+    guess: u32,
+    low: u32,
+    high: u32,
+    optimal: u32,
+    in_interval: bool,
+    efficiency: f64,
+}
+
+impl GuessAnalysis {
+    fn new(guess: u32, low: u32, high: u32) -> Self {
+        let optimal = low + (high - low) / 2;
+        let size = (high - low) as f64 + 1.0;
+        let remaining = worst_case_remaining(guess, low, high);
+        let optimal_remaining = worst_case_remaining(optimal, low, high);
+
+        GuessAnalysis {
+            guess,
+            low,
+            high,
+            optimal,
+            in_interval: (low..=high).contains(&guess),
+            // part des nombres elimines dans le pire cas, par rapport a l'optimum
+            efficiency: (size - remaining) / (size - optimal_remaining) * 100.0,
+        }
+    }
+}
+
+// nombre de candidats restant dans le pire cas apres cet essai
+fn worst_case_remaining(guess: u32, low: u32, high: u32) -> f64 {
+    if (low..=high).contains(&guess) {
+        (guess - low).max(high - guess) as f64
+    } else {
+        (high - low) as f64 + 1.0
+    }
+}
+
+fn display_analysis(analysis: &[GuessAnalysis]) {
+    if analysis.is_empty() {
+        return;
+    }
+
+    println!("\nGuess analysis:");
+    println!("  {:>3}  {:>8}  {:>20}  {:>8}  {:>10}", "#", "Guess", "Possible range", "Optimal", "Efficiency");
+    for (i, guess) in analysis.iter().enumerate() {
+        let range = format!("{}-{}", guess.low, guess.high);
+        println!("  {:>3}  {:>8}  {:>20}  {:>8}  {:>9.0}%", i + 1, guess.guess, range, guess.optimal, guess.efficiency);
+    }
+
+    let efficiency = analysis.iter().map(|guess| guess.efficiency).sum::<f64>() / analysis.len() as f64;
+    println!("Efficiency: {:.0}%", efficiency);
+}
+
+fn select_difficulty() -> Option<Difficulty> {
+    println!("Select a difficulty:");
+    println!("1. Easy (1-50)");
+    println!("2. Normal (1-100)");
+    println!("3. Hard (1-1,000)");
+    println!("4. Expert (1-1,000,000)");
+    println!("5. Custom range");
+
+    let choice = utils::get_valid_choice();
+    Difficulty::from_choice(choice)
+}
+
+fn select_custom_range() -> Option<Difficulty> {
+    println!("Enter the lowest number:");
+    let min = get_valid_number();
+    println!("Enter the highest number:");
+    let max = get_valid_number();
+
+    if max <= min {
+        println!("The highest number must be greater than the lowest one.");
+        return None;
+    }
+    Some(Difficulty::Custom { min, max })
+}
+
+fn generate_random_number(min: u32, max: u32) -> u32 {
   let mut rng = rand::rng();
-  rng.random_range(1..=100)
+  rng.random_range(min..=max)
 }
 
 fn get_valid_number() -> u32 {
@@ -0,0 +1,183 @@
+use std::error::Error;
+use rand::Rng;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::utils;
+use crate::words;
+
+pub const GAME_NAME: &str = "hangman";
+const MAX_LIVES: usize = 6;
+
+// une etape du pendu par vie perdue
+const GALLOWS: [&str; MAX_LIVES + 1] = [
+    "  +---+\n  |   |\n      |\n      |\n      |\n      |\n=========",
+    "  +---+\n  |   |\n  O   |\n      |\n      |\n      |\n=========",
+    "  +---+\n  |   |\n  O   |\n  |   |\n      |\n      |\n=========",
+    "  +---+\n  |   |\n  O   |\n /|   |\n      |\n      |\n=========",
+    "  +---+\n  |   |\n  O   |\n /|\\  |\n      |\n      |\n=========",
+    "  +---+\n  |   |\n  O   |\n /|\\  |\n /    |\n      |\n=========",
+    "  +---+\n  |   |\n  O   |\n /|\\  |\n / \\  |\n      |\n=========",
+];
+
+#[derive(PartialEq)]
+enum GameState {
+    InProgress,
+    Won,
+    Lost,
+}
+
+struct Round<'a> {
+    secret_word: &'a str,
+    used_letters: Vec<char>,
+    lives: usize,
+    state: GameState,
+}
+
+impl<'a> Round<'a> {
+    fn new(secret_word: &'a str) -> Self {
+        Round {
+            secret_word,
+            used_letters: Vec::new(),
+            lives: MAX_LIVES,
+            state: GameState::InProgress,
+        }
+    }
+
+    // les caracteres qui ne sont pas des lettres (tirets, espaces) sont affiches d'office
+    fn masked_word(&self) -> String {
+        self.secret_word
+            .chars()
+            .map(|c| {
+                if !c.is_alphabetic() || self.used_letters.contains(&c) {
+                    c.to_string()
+                } else {
+                    "_".to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn is_found(&self) -> bool {
+        self.secret_word
+            .chars()
+            .all(|c| !c.is_alphabetic() || self.used_letters.contains(&c))
+    }
+
+    fn guess_letter(&mut self, letter: char) -> bool {
+        self.used_letters.push(letter);
+        let found = self.secret_word.contains(letter);
+
+        if self.is_found() {
+            self.state = GameState::Won;
+        } else if !found {
+            self.lose_life();
+        }
+        found
+    }
+
+    fn guess_word(&mut self, word: &str) -> bool {
+        if word == self.secret_word {
+            self.state = GameState::Won;
+            return true;
+        }
+        self.lose_life();
+        false
+    }
+
+    fn lose_life(&mut self) {
+        self.lives -= 1;
+        if self.lives == 0 {
+            self.state = GameState::Lost;
+        }
+    }
+
+    fn score(&self) -> i32 {
+        match self.state {
+            GameState::Won => self.lives as i32 * 10,
+            _ => 0,
+        }
+    }
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Hangman Game !\n");
+
+    let theme = match words::select_theme() {
+        Some(theme) => theme,
+        None => {
+            println!("Invalid choice. Exiting game.");
+            return Ok(());
+        }
+    };
+
+    println!("You selected the theme: {}", theme.as_str());
+
+    let words = theme.load_words()?;
+    if words.is_empty() {
+        println!("No words found for the selected theme. Exiting game.");
+        return Ok(());
+    }
+
+    loop {
+        let mut rng = rand::rng();
+        let secret_word = &words[rng.random_range(0..words.len())];
+        let mut round = Round::new(secret_word);
+
+        while round.state == GameState::InProgress {
+            display_round(&round);
+            let input = utils::remove_accents(utils::get_valid_input("Guess a letter or the whole word: ").trim());
+            let letters: Vec<char> = input.chars().collect();
+
+            if letters.len() == 1 {
+                let letter = letters[0];
+                if !letter.is_alphabetic() {
+                    println!("Please enter a letter.");
+                } else if round.used_letters.contains(&letter) {
+                    println!("You already tried '{}'.", letter);
+                } else if round.guess_letter(letter) {
+                    println!("Good guess!");
+                } else {
+                    println!("'{}' is not in the word.", letter);
+                }
+            } else if !round.guess_word(&input) {
+                println!("'{}' is not the word.", input);
+            }
+        }
+
+        display_round(&round);
+        match round.state {
+            GameState::Won => println!("Congrats! You found the word: {}", secret_word),
+            _ => println!("You have been hanged! The word was: {}", secret_word),
+        }
+
+        let score = round.score();
+        println!("Your score is: {}", score);
+        match DB::add_game_score(GAME_NAME, user_id, &username, score) {
+            Ok(_) => {},
+            Err(e) => println!("Failed to save score: {}", e),
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn display_round(round: &Round) {
+    println!("\n{}\n", GALLOWS[MAX_LIVES - round.lives]);
+    println!("Word: {}", round.masked_word());
+    println!("Used letters: {}", round.used_letters.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(", "));
+    println!("Lives left: {}", round.lives);
+}
@@ -0,0 +1,302 @@
+use std::error::Error;
+use rand::Rng;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::utils;
+
+pub const GAME_NAME: &str = "mastermind";
+const MAX_GUESSES: usize = 10;
+const COLOR_SYMBOLS: [char; 8] = ['R', 'G', 'B', 'Y', 'O', 'P', 'W', 'K'];
+const COLOR_NAMES: [&str; 8] = ["red", "green", "blue", "yellow", "orange", "purple", "white", "black"];
+const DIGIT_SYMBOLS: [char; 8] = ['1', '2', '3', '4', '5', '6', '7', '8'];
+// au dela, le solveur ne fait plus le minimax complet (trop lent)
+const MINIMAX_BUDGET: usize = 5_000_000;
+// seul le reglage classique (4 pions, 6 couleurs, doublons permis) est classe
+const STANDARD_CODE_LENGTH: usize = 4;
+const STANDARD_SYMBOL_COUNT: u8 = 6;
+
+type Code = Vec<u8>;
+
+#[derive(Clone, Copy)]
+enum Symbols {
+    Colors,
+    Digits,
+}
+
+impl Symbols {
+    fn list(&self) -> &'static [char; 8] {
+        match self {
+            Symbols::Colors => &COLOR_SYMBOLS,
+            Symbols::Digits => &DIGIT_SYMBOLS,
+        }
+    }
+}
+
+struct Settings {
+    symbols: Symbols,
+    code_length: usize,
+    symbol_count: u8,
+    duplicates: bool,
+}
+
+impl Settings {
+    fn is_standard(&self) -> bool {
+        self.code_length == STANDARD_CODE_LENGTH && self.symbol_count == STANDARD_SYMBOL_COUNT && self.duplicates
+    }
+
+    fn format_code(&self, code: &[u8]) -> String {
+        code.iter().map(|&peg| self.symbols.list()[peg as usize]).collect()
+    }
+
+    fn parse_code(&self, input: &str) -> Option<Code> {
+        let code: Option<Code> = input
+            .trim()
+            .to_uppercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                self.symbols.list()[..self.symbol_count as usize]
+                    .iter()
+                    .position(|&symbol| symbol == c)
+                    .map(|peg| peg as u8)
+            })
+            .collect();
+        code.filter(|code| code.len() == self.code_length)
+    }
+
+    fn random_code(&self) -> Code {
+        let mut rng = rand::rng();
+        let mut code = Code::new();
+        while code.len() < self.code_length {
+            let peg = rng.random_range(0..self.symbol_count);
+            if self.duplicates || !code.contains(&peg) {
+                code.push(peg);
+            }
+        }
+        code
+    }
+
+    fn all_codes(&self) -> Vec<Code> {
+        let mut codes: Vec<Code> = vec![Code::new()];
+        for _ in 0..self.code_length {
+            codes = codes
+                .into_iter()
+                .flat_map(|code| {
+                    (0..self.symbol_count)
+                        .filter(|peg| self.duplicates || !code.contains(peg))
+                        .map(|peg| {
+                            let mut next = code.clone();
+                            next.push(peg);
+                            next
+                        })
+                        .collect::<Vec<Code>>()
+                })
+                .collect();
+        }
+        codes
+    }
+}
+
+// (noirs, blancs) : bonne couleur bien placee / bonne couleur mal placee
+fn score_guess(secret: &[u8], guess: &[u8]) -> (usize, usize) {
+    let mut black = 0;
+    let mut secret_counts = [0usize; COLOR_SYMBOLS.len()];
+    let mut guess_counts = [0usize; COLOR_SYMBOLS.len()];
+    for (&s, &g) in secret.iter().zip(guess) {
+        if s == g {
+            black += 1;
+        }
+        secret_counts[s as usize] += 1;
+        guess_counts[g as usize] += 1;
+    }
+    let common: usize = secret_counts.iter().zip(&guess_counts).map(|(s, g)| *s.min(g)).sum();
+    (black, common - black)
+}
+
+// solveur facon Knuth : garde les codes compatibles et joue le code qui
+// minimise le nombre de candidats restants dans le pire des cas
+struct Solver {
+    all_codes: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl Solver {
+    fn new(settings: &Settings) -> Self {
+        let all_codes = settings.all_codes();
+        Solver {
+            candidates: all_codes.clone(),
+            all_codes,
+        }
+    }
+
+    fn next_guess(&self) -> Code {
+        if self.candidates.len() <= 2 || self.candidates.len() * self.all_codes.len() > MINIMAX_BUDGET {
+            return self.candidates[0].clone();
+        }
+
+        let code_length = self.candidates[0].len();
+        self.all_codes
+            .iter()
+            .min_by_key(|guess| {
+                let mut buckets = vec![0usize; (code_length + 1) * (code_length + 1)];
+                for candidate in &self.candidates {
+                    let (black, white) = score_guess(candidate, guess);
+                    buckets[black * (code_length + 1) + white] += 1;
+                }
+                // a egalite, on prefere un code qui peut etre la solution
+                let is_candidate = buckets[code_length * (code_length + 1)] > 0;
+                let worst = buckets.into_iter().max().unwrap_or(0);
+                (worst, !is_candidate)
+            })
+            .cloned()
+            .unwrap_or_else(|| self.candidates[0].clone())
+    }
+
+    fn apply_feedback(&mut self, guess: &[u8], feedback: (usize, usize)) {
+        self.candidates.retain(|candidate| score_guess(candidate, guess) == feedback);
+    }
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Mastermind Game !\n");
+
+    println!("1. Play");
+    println!("2. Watch the computer play");
+    let demo = utils::get_valid_choice() == 2;
+
+    let settings = select_settings();
+    display_rules(&settings);
+
+    loop {
+        if demo {
+            watch_computer_play(&settings);
+        } else {
+            let guesses_used = play_round(&settings);
+            let score = match guesses_used {
+                Some(guesses_used) => ((MAX_GUESSES - guesses_used + 1) * 10) as i32,
+                None => 0,
+            };
+            println!("Your score is: {}", score);
+            if settings.is_standard() {
+                match DB::add_game_score(GAME_NAME, user_id, &username, score) {
+                    Ok(_) => {},
+                    Err(e) => println!("Failed to save score: {}", e),
+                }
+            } else {
+                println!(
+                    "Only the standard setting ({} pegs, {} symbols, duplicates allowed) counts for the leaderboard.",
+                    STANDARD_CODE_LENGTH, STANDARD_SYMBOL_COUNT
+                );
+            }
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// renvoie le nombre d'essais utilises si le code a ete trouve
+fn play_round(settings: &Settings) -> Option<usize> {
+    let secret = settings.random_code();
+    // println!("The secret code is: {}", settings.format_code(&secret));
+
+    for attempt in 1..=MAX_GUESSES {
+        let guess = loop {
+            let input = utils::get_valid_input(&format!("Guess {}/{}:", attempt, MAX_GUESSES));
+            match settings.parse_code(&input) {
+                Some(guess) => break guess,
+                None => println!("Invalid code. Please enter {} symbols from the list above.", settings.code_length),
+            }
+        };
+
+        let (black, white) = score_guess(&secret, &guess);
+        println!("{}  black: {}  white: {}", settings.format_code(&guess), black, white);
+        if black == settings.code_length {
+            println!("Congrats! You cracked the code in {} guesses!", attempt);
+            return Some(attempt);
+        }
+    }
+
+    println!("No guesses left! The code was: {}", settings.format_code(&secret));
+    None
+}
+
+fn watch_computer_play(settings: &Settings) {
+    let secret = settings.random_code();
+    let mut solver = Solver::new(settings);
+    println!("Secret code: {}", settings.format_code(&secret));
+
+    for attempt in 1.. {
+        let guess = solver.next_guess();
+        let feedback = score_guess(&secret, &guess);
+        solver.apply_feedback(&guess, feedback);
+        println!(
+            "Guess {}: {}  black: {}  white: {}  ({} possible codes left)",
+            attempt,
+            settings.format_code(&guess),
+            feedback.0,
+            feedback.1,
+            solver.candidates.len()
+        );
+        if feedback.0 == settings.code_length {
+            println!("The computer cracked the code in {} guesses.", attempt);
+            break;
+        }
+    }
+}
+
+fn select_settings() -> Settings {
+    println!("Play with:");
+    println!("1. Colors");
+    println!("2. Digits");
+    let symbols = match utils::get_valid_choice() {
+        2 => Symbols::Digits,
+        _ => Symbols::Colors,
+    };
+
+    println!("Code length (3-6):");
+    let code_length = utils::get_valid_choice().clamp(3, 6) as usize;
+
+    println!("Number of colors/digits (4-8):");
+    let symbol_count = utils::get_valid_choice().clamp(4, 8) as u8;
+
+    let mut duplicates = utils::confirm("Allow duplicates in the code? (y/n)");
+    if !duplicates && (symbol_count as usize) < code_length {
+        println!("Not enough symbols for a code without duplicates, duplicates are allowed.");
+        duplicates = true;
+    }
+
+    Settings {
+        symbols,
+        code_length,
+        symbol_count,
+        duplicates,
+    }
+}
+
+fn display_rules(settings: &Settings) {
+    println!("\nFind the secret code of {} pegs in {} guesses.", settings.code_length, MAX_GUESSES);
+    match settings.symbols {
+        Symbols::Colors => {
+            let colors: Vec<String> = (0..settings.symbol_count as usize)
+                .map(|i| format!("{} = {}", COLOR_SYMBOLS[i], COLOR_NAMES[i]))
+                .collect();
+            println!("Colors: {}", colors.join(", "));
+        }
+        Symbols::Digits => println!("Digits: 1 to {}", settings.symbol_count),
+    }
+    println!("Duplicates: {}", if settings.duplicates { "allowed" } else { "not allowed" });
+    println!("Black = right symbol in the right place, white = right symbol in the wrong place.\n");
+}
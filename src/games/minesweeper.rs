@@ -0,0 +1,295 @@
+use std::error::Error;
+use std::time::Instant;
+use rand::Rng;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::utils;
+
+#[derive(Clone, Copy)]
+enum Difficulty {
+    Beginner,
+    Intermediate,
+    Expert,
+}
+
+impl Difficulty {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(Difficulty::Beginner),
+            2 => Some(Difficulty::Intermediate),
+            3 => Some(Difficulty::Expert),
+            _ => None,
+        }
+    }
+
+    // (lignes, colonnes, mines)
+    fn dimensions(&self) -> (usize, usize, usize) {
+        match self {
+            Difficulty::Beginner => (9, 9, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (16, 30, 99),
+        }
+    }
+
+    fn table_name(&self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "minesweeper_beginner",
+            Difficulty::Intermediate => "minesweeper_intermediate",
+            Difficulty::Expert => "minesweeper_expert",
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum GameState {
+    InProgress,
+    Won,
+    Lost,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CellState {
+    Hidden,
+    Flagged,
+    Revealed,
+}
+
+enum Command {
+    Reveal(usize, usize),
+    Flag(usize, usize),
+}
+
+struct Board {
+    rows: usize,
+    cols: usize,
+    mine_count: usize,
+    mines: Vec<bool>,
+    cells: Vec<CellState>,
+    // les mines ne sont posees qu'au premier clic, pour qu'il soit sans danger
+    mines_placed: bool,
+    state: GameState,
+}
+
+impl Board {
+    fn new(difficulty: Difficulty) -> Self {
+        let (rows, cols, mine_count) = difficulty.dimensions();
+        Board {
+            rows,
+            cols,
+            mine_count,
+            mines: vec![false; rows * cols],
+            cells: vec![CellState::Hidden; rows * cols],
+            mines_placed: false,
+            state: GameState::InProgress,
+        }
+    }
+
+    fn neighbours(&self, index: usize) -> Vec<usize> {
+        let (row, col) = (index / self.cols, index % self.cols);
+        let mut neighbours = Vec::new();
+        for r in row.saturating_sub(1)..=(row + 1).min(self.rows - 1) {
+            for c in col.saturating_sub(1)..=(col + 1).min(self.cols - 1) {
+                if (r, c) != (row, col) {
+                    neighbours.push(r * self.cols + c);
+                }
+            }
+        }
+        neighbours
+    }
+
+    // ni le premier clic ni ses voisins ne peuvent etre des mines
+    fn place_mines(&mut self, first_click: usize) {
+        let mut safe = self.neighbours(first_click);
+        safe.push(first_click);
+
+        let mut rng = rand::rng();
+        let mut placed = 0;
+        while placed < self.mine_count {
+            let index = rng.random_range(0..self.mines.len());
+            if !self.mines[index] && !safe.contains(&index) {
+                self.mines[index] = true;
+                placed += 1;
+            }
+        }
+        self.mines_placed = true;
+    }
+
+    fn adjacent_mines(&self, index: usize) -> usize {
+        self.neighbours(index).into_iter().filter(|&i| self.mines[i]).count()
+    }
+
+    fn reveal(&mut self, index: usize) {
+        if !self.mines_placed {
+            self.place_mines(index);
+        }
+        if self.mines[index] {
+            self.cells[index] = CellState::Revealed;
+            self.state = GameState::Lost;
+            return;
+        }
+
+        // propagation : les cases sans mine voisine devoilent leurs voisines
+        let mut to_reveal = vec![index];
+        while let Some(current) = to_reveal.pop() {
+            if self.cells[current] == CellState::Revealed {
+                continue;
+            }
+            self.cells[current] = CellState::Revealed;
+            if self.adjacent_mines(current) == 0 {
+                to_reveal.extend(
+                    self.neighbours(current)
+                        .into_iter()
+                        .filter(|&i| self.cells[i] == CellState::Hidden),
+                );
+            }
+        }
+
+        let hidden_safe_cells = (0..self.cells.len())
+            .filter(|&i| !self.mines[i] && self.cells[i] != CellState::Revealed)
+            .count();
+        if hidden_safe_cells == 0 {
+            self.state = GameState::Won;
+        }
+    }
+
+    fn toggle_flag(&mut self, index: usize) {
+        self.cells[index] = match self.cells[index] {
+            CellState::Hidden => CellState::Flagged,
+            CellState::Flagged => CellState::Hidden,
+            CellState::Revealed => CellState::Revealed,
+        };
+    }
+
+    fn flags_left(&self) -> i32 {
+        self.mine_count as i32 - self.cells.iter().filter(|&&cell| cell == CellState::Flagged).count() as i32
+    }
+
+    fn render(&self) -> String {
+        let header: String = (1..=self.cols).map(|col| format!("{:>3}", col)).collect();
+        let mut lines = vec![format!("  {}", header)];
+        for row in 0..self.rows {
+            let cells: String = (0..self.cols)
+                .map(|col| format!("{:>3}", self.cell_symbol(row * self.cols + col)))
+                .collect();
+            lines.push(format!("{} {}", row_label(row), cells));
+        }
+        lines.join("\n")
+    }
+
+    fn cell_symbol(&self, index: usize) -> char {
+        // en fin de partie, toutes les mines sont montrees
+        if self.state != GameState::InProgress && self.mines[index] {
+            return '*';
+        }
+        match self.cells[index] {
+            CellState::Hidden => '#',
+            CellState::Flagged => 'F',
+            CellState::Revealed => match self.adjacent_mines(index) {
+                0 => '.',
+                count => char::from_digit(count as u32, 10).unwrap_or('?'),
+            },
+        }
+    }
+
+    // "r B12" devoile, "f B12" pose ou retire un drapeau, "B12" seul devoile
+    fn parse_command(&self, input: &str) -> Option<Command> {
+        let input = input.trim().to_uppercase();
+        let (flag, coordinate) = match input.split_once(' ') {
+            Some(("R", coordinate)) => (false, coordinate.trim()),
+            Some(("F", coordinate)) => (true, coordinate.trim()),
+            Some(_) => return None,
+            None => (false, input.as_str()),
+        };
+
+        let mut chars = coordinate.chars();
+        let row = u8::try_from(chars.next()?).ok()?.checked_sub(b'A')? as usize;
+        let col = chars.as_str().parse::<usize>().ok()?.checked_sub(1)?;
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+
+        match flag {
+            true => Some(Command::Flag(row, col)),
+            false => Some(Command::Reveal(row, col)),
+        }
+    }
+}
+
+fn row_label(row: usize) -> char {
+    (b'A' + row as u8) as char
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Minesweeper Game !\n");
+
+    println!("Select a difficulty:");
+    println!("1. Beginner (9x9, 10 mines)");
+    println!("2. Intermediate (16x16, 40 mines)");
+    println!("3. Expert (16x30, 99 mines)");
+    let difficulty = match Difficulty::from_choice(utils::get_valid_choice()) {
+        Some(difficulty) => difficulty,
+        None => {
+            println!("Invalid choice. Exiting game.");
+            return Ok(());
+        }
+    };
+
+    println!("\nReveal a cell with 'r B12' (or just 'B12'), flag it with 'f B12'.");
+    println!("Your first click is always safe. The timer starts with it.");
+
+    loop {
+        let mut board = Board::new(difficulty);
+        let mut start_time: Option<Instant> = None;
+
+        while board.state == GameState::InProgress {
+            let elapsed = start_time.map(|start| start.elapsed().as_secs()).unwrap_or(0);
+            println!("\n{}\n", board.render());
+            println!("Flags left: {}  Time: {}s", board.flags_left(), elapsed);
+
+            let input = utils::get_valid_input("Your move:");
+            match board.parse_command(&input) {
+                Some(Command::Reveal(row, col)) => {
+                    let index = row * board.cols + col;
+                    match board.cells[index] {
+                        CellState::Flagged => println!("This cell is flagged, remove the flag first."),
+                        CellState::Revealed => println!("This cell is already revealed."),
+                        CellState::Hidden => {
+                            start_time.get_or_insert_with(Instant::now);
+                            board.reveal(index);
+                        }
+                    }
+                }
+                Some(Command::Flag(row, col)) => board.toggle_flag(row * board.cols + col),
+                None => println!("Invalid move. Use 'r B12' or 'f B12' with a cell of the board."),
+            }
+        }
+
+        let seconds = start_time.map(|start| start.elapsed().as_secs()).unwrap_or(0);
+        println!("\n{}\n", board.render());
+        match board.state {
+            GameState::Won => {
+                println!("Congrats! You cleared the board in {} seconds!", seconds);
+                match DB::add_game_score(difficulty.table_name(), user_id, &username, seconds as i32) {
+                    Ok(_) => {},
+                    Err(e) => println!("Failed to save score: {}", e),
+                }
+            }
+            _ => println!("Boom! You hit a mine after {} seconds.", seconds),
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
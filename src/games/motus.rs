@@ -1,61 +1,106 @@
-use reqwest::blocking;
 use std::error::Error;
+use std::fs;
 use rand::Rng;
 
 use crate::db::DbManager as DB;
 use crate::User;
 use crate::utils;
+use crate::words;
+use crate::games::motus_solver::{evaluate_guess, LetterFeedback, Solver};
 
 pub const GAME_NAME: &str = "motus";
+const MAX_ATTEMPTS: i32 = 10;
+const LETTER_HINT_COST: i32 = 5;
+const WORD_HINT_COST: i32 = 10;
 
-#[derive(serde::Deserialize)]
-struct ApiWord {
-    name: String,
-    #[serde(default)] // Rend le champ optionnel
-    _categorie: Option<String>,
+#[derive(PartialEq)]
+enum GameState {
+    InProgress,
+    Won,
+    Lost,
 }
 
-enum Theme {
-    English,
-    FrAgriculture,
-    FrArmee,
-    FrAnimaux,
-    FrIndustrie,
-    FrNourriture,
+struct Round<'a> {
+    secret_chars: Vec<char>,
+    secret_word: &'a str,
+    attempts: i32,
+    state: GameState,
+    solver: Solver,
+    history: Vec<(String, Vec<LetterFeedback>)>,
+    initial_candidates: usize,
+    candidate_counts: Vec<usize>,
+    revealed: Vec<usize>,
+    hint_penalty: i32,
 }
 
-impl Theme {
-    fn from_choice(choice: u32) -> Option<Self> {
-        match choice {
-            1 => Some(Theme::English),
-            2 => Some(Theme::FrAgriculture),
-            3 => Some(Theme::FrArmee),
-            4 => Some(Theme::FrAnimaux),
-            5 => Some(Theme::FrIndustrie),
-            6 => Some(Theme::FrNourriture),
-            _ => None,
+impl<'a> Round<'a> {
+    fn new(secret_word: &'a str, words: &[String]) -> Self {
+        let secret_chars: Vec<char> = secret_word.chars().collect();
+        let solver = Solver::new(words, secret_chars.len());
+        Round {
+            secret_chars,
+            secret_word,
+            attempts: MAX_ATTEMPTS,
+            state: GameState::InProgress,
+            initial_candidates: solver.candidates().len(),
+            solver,
+            history: Vec::new(),
+            candidate_counts: Vec::new(),
+            revealed: Vec::new(),
+            hint_penalty: 0,
         }
     }
 
-    fn as_str(&self) -> &'static str {
-        match self {
-            Theme::English => "english words",
-            Theme::FrAgriculture => "french agriculture",
-            Theme::FrArmee => "french army",
-            Theme::FrAnimaux => "french animals",
-            Theme::FrIndustrie => "french industry",
-            Theme::FrNourriture => "french food",
+    fn word_length(&self) -> usize {
+        self.secret_chars.len()
+    }
+
+    fn submit_guess(&mut self, guess: &str) -> Vec<LetterFeedback> {
+        let feedback = evaluate_guess(self.secret_word, guess);
+        self.solver.apply_feedback(guess, &feedback);
+        self.candidate_counts.push(self.solver.candidates().len());
+        self.history.push((guess.to_string(), feedback.clone()));
+
+        if guess == self.secret_word {
+            self.state = GameState::Won;
+        } else {
+            self.attempts -= 1;
+            if self.attempts == 0 {
+                self.state = GameState::Lost;
+            }
         }
+
+        feedback
+    }
+
+    // positions deja trouvees par le joueur ou revelees par un indice
+    fn is_known(&self, index: usize) -> bool {
+        self.revealed.contains(&index)
+            || self
+                .history
+                .iter()
+                .any(|(_, feedback)| feedback[index] == LetterFeedback::Correct)
     }
 
-    fn api_endpoint(&self) -> &'static str {
-        match self {
-            Theme::English => "https://random-word-api.herokuapp.com/word?number=50&length=5",
-            Theme::FrAgriculture => "https://trouve-mot.fr/api/categorie/11/50",
-            Theme::FrArmee => "https://trouve-mot.fr/api/categorie/26/50",
-            Theme::FrAnimaux => "https://trouve-mot.fr/api/categorie/19/50",
-            Theme::FrIndustrie => "https://trouve-mot.fr/api/categorie/9/50",
-            Theme::FrNourriture => "https://trouve-mot.fr/api/categorie/5/50",
+    fn reveal_letter(&mut self) -> Option<(usize, char)> {
+        let index = (0..self.word_length()).find(|&index| !self.is_known(index))?;
+        let letter = self.secret_chars[index];
+        self.revealed.push(index);
+        self.solver.apply_revealed_letter(index, letter);
+        self.hint_penalty += LETTER_HINT_COST;
+        Some((index, letter))
+    }
+
+    fn suggest_word(&mut self) -> Option<String> {
+        let suggestion = self.solver.suggest()?.to_string();
+        self.hint_penalty += WORD_HINT_COST;
+        Some(suggestion)
+    }
+
+    fn score(&self) -> i32 {
+        match self.state {
+            GameState::Won => (self.attempts * 10 - self.hint_penalty).max(0),
+            _ => 0,
         }
     }
 }
@@ -67,7 +112,13 @@ pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
     utils::clear_terminal();
     println!("Welcome on the Motus Game !\n");
 
-    let theme = match select_theme() {
+    println!("1. Play");
+    println!("2. Share a past game");
+    if utils::get_valid_choice() == 2 {
+        return share_past_game(user_id);
+    }
+
+    let theme = match words::select_theme() {
         Some(theme) => theme,
         None => {
             println!("Invalid choice. Exiting game.");
@@ -77,67 +128,71 @@ pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
 
     println!("You selected the theme: {}", theme.as_str());
 
-    let words = fetch_words_from_api(theme.api_endpoint())?;
+    let words = theme.load_words()?;
+    println!("Loaded {} words.", words.len());
     if words.is_empty() {
         println!("No words found for the selected theme. Exiting game.");
         return Ok(());
     }
         
     loop {
-        let mut attempts = 10;
         let mut rng = rand::rng();
         let secret_word = &words[rng.random_range(0..words.len())];
-        let secret_chars: Vec<char> = secret_word.chars().collect();
-        
-        let mut correct_chars: Vec<char> = vec![' '; secret_word.len()];
-        let mut misplaced_chars: Vec<char>;
-        
+        let mut round = Round::new(secret_word, &words);
+
         //  println!("{} is the secret word!", secret_word);
-        println!("The secret word contains {} letters.", secret_word.len());
-        loop {
+        println!("The secret word contains {} letters.", round.word_length());
+        println!("Type '/hint' for a hint (costs points) or '/solve' to see the remaining candidates.");
+        while round.state == GameState::InProgress {
             let input = utils::get_valid_input("Please enter your guess: ");
-            let input = utils::remove_accents(input.trim());
 
-            if !input.len().eq(&secret_word.len()) && attempts > 0 {
-                    attempts -= 1;
-                    println!("Word length mismatch. You have {} attempts left.", attempts);
+            match input.trim() {
+                "/hint" => {
+                    give_hint(&mut round);
                     continue;
-            }
-            let users_chars: Vec<char> = input.trim().chars().collect();
-            misplaced_chars = Vec::new();
-
-            for (index, &user_char) in users_chars.iter().enumerate() {
-                if let Some(&secret_char) = secret_chars.get(index) {
-                    if secret_char == user_char {
-                        correct_chars[index] = user_char;
-                    } else {
-                        correct_chars[index] = ' ';
-                        if secret_chars.contains(&user_char) {
-                            misplaced_chars.push(user_char);
-                        }
-                    }
-                } else {
-                    println!("Index out of bounds: {}", index);
-                    break;
                 }
-            }
-            if correct_chars.iter().all(|&c| c != ' ') {
-                println!("Congrats! You guessed the word: {}", secret_word);
-                let score = attempts * 10;
-                match DB::add_game_score(GAME_NAME, user_id, &username, score) {
-                    Ok(_) => {},
-                    Err(e) => println!("Failed to save score: {}", e),
+                "/solve" => {
+                    display_solver_progress(&round);
+                    continue;
                 }
-                break;
+                _ => {}
             }
 
-            if attempts > 0 {
-                attempts -= 1;
+            let input = utils::remove_accents(input.trim());
+
+            // une longueur invalide ne coute pas d'essai
+            if input.chars().count() != round.word_length() {
+                println!("Your guess must contain {} letters. Please try again.", round.word_length());
+                continue;
             }
 
-            println!("Correct letters in the right position: {:?}", correct_chars);
-            println!("Misplaced letters: {:?}", misplaced_chars);
-            println!("You have {} attempts left.", attempts);
+            let feedback = round.submit_guess(&input);
+
+            match round.state {
+                GameState::InProgress => {
+                    display_feedback(&input, &feedback);
+                    println!("You have {} attempts left.", round.attempts);
+                }
+                GameState::Won => println!("Congrats! You guessed the word: {}", secret_word),
+                GameState::Lost => println!("No attempts left! The word was: {}", secret_word),
+            }
+        }
+
+        let score = round.score();
+        println!("Your score is: {}", score);
+        match DB::add_game_score(GAME_NAME, user_id, &username, score) {
+            Ok(_) => {},
+            Err(e) => println!("Failed to save score: {}", e),
+        }
+
+        let attempts_used = round.history.len() as i32;
+        let won = round.state == GameState::Won;
+        let grid = emoji_grid(&round.history);
+        let summary = share_summary(theme.as_str(), attempts_used, won, &grid);
+        println!("\n{}\n", summary);
+        match DB::add_motus_history(user_id, theme.as_str(), attempts_used, won, &grid) {
+            Ok(id) => offer_to_save_summary(id, &summary),
+            Err(e) => println!("Failed to save game history: {}", e),
         }
 
         match utils::play_again() {
@@ -152,36 +207,112 @@ pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn select_theme() -> Option<Theme> {
-    println!("Select a theme: ");
-    println!("1. English");
-    println!("2. French Agriculture");
-    println!("3. French Army");
-    println!("4. French Animals");
-    println!("5. French Industry");
-    println!("6. French Food");
-
-    let choice = utils::get_valid_choice();
-    Theme::from_choice(choice)
+fn display_feedback(guess: &str, feedback: &[LetterFeedback]) {
+    let mut correct_chars: Vec<char> = Vec::new();
+    let mut misplaced_chars: Vec<char> = Vec::new();
+
+    for (user_char, letter) in guess.chars().zip(feedback) {
+        match letter {
+            LetterFeedback::Correct => correct_chars.push(user_char),
+            LetterFeedback::Misplaced => {
+                correct_chars.push(' ');
+                misplaced_chars.push(user_char);
+            }
+            LetterFeedback::Absent => correct_chars.push(' '),
+        }
+    }
+
+    println!("Correct letters in the right position: {:?}", correct_chars);
+    println!("Misplaced letters: {:?}", misplaced_chars);
+}
+
+fn give_hint(round: &mut Round) {
+    println!("1. Reveal a letter (-{} points)", LETTER_HINT_COST);
+    println!("2. Suggest a possible word (-{} points)", WORD_HINT_COST);
+    println!("3. Cancel");
+
+    match utils::get_valid_choice() {
+        1 => match round.reveal_letter() {
+            Some((index, letter)) => println!("Letter {} is '{}'.", index + 1, letter),
+            None => println!("You already know every letter!"),
+        },
+        2 => match round.suggest_word() {
+            Some(word) => println!("Try the word: {}", word),
+            None => println!("No word of the list matches your guesses."),
+        },
+        _ => println!("No hint used."),
+    }
 }
 
-fn fetch_words_from_api(api_url: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let response = blocking::get(api_url)?;
-    if !response.status().is_success() {
-        return Err(format!("Failed to fetch words: HTTP {}", response.status()).into());
+fn display_solver_progress(round: &Round) {
+    println!("Candidates at start: {}", round.initial_candidates);
+    for (i, ((guess, _), count)) in round.history.iter().zip(&round.candidate_counts).enumerate() {
+        println!("  Guess {} ({}): {} candidates left", i + 1, guess, count);
     }
+    println!("Candidates remaining now: {}", round.solver.candidates().len());
+}
+
+fn emoji_grid(history: &[(String, Vec<LetterFeedback>)]) -> String {
+    history
+        .iter()
+        .map(|(_, feedback)| {
+            feedback
+                .iter()
+                .map(|letter| match letter {
+                    LetterFeedback::Correct => '🟩',
+                    LetterFeedback::Misplaced => '🟨',
+                    LetterFeedback::Absent => '⬛',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
 
-    if api_url.contains("random-word-api") {
-        let words: Vec<String> = response.json()?;
-        println!("Fetched {} words from API.", words.len());
-        Ok(words)
+// resume sans le mot secret, a partager tel quel
+fn share_summary(theme: &str, attempts_used: i32, won: bool, grid: &str) -> String {
+    let result = if won {
+        attempts_used.to_string()
     } else {
-        let api_words: Vec<ApiWord> = response.json()?;
-        let words: Vec<String> = api_words
-            .into_iter()
-            .map(|word| utils::remove_accents(&word.name))
-            .collect();
-        println!("Fetched {} words from API.", words.len());
-        Ok(words)
-    }
-}
\ No newline at end of file
+        "X".to_string()
+    };
+    format!("Motus - {} - {}/{}\n{}", theme, result, MAX_ATTEMPTS, grid)
+}
+
+fn offer_to_save_summary(game_id: i64, summary: &str) {
+    if !utils::confirm("Save this summary to a file? (y/n)") {
+        return;
+    }
+
+    let path = format!("data/shares/motus_{}.txt", game_id);
+    match fs::create_dir_all("data/shares").and_then(|_| fs::write(&path, summary)) {
+        Ok(_) => println!("Summary saved to {}", path),
+        Err(e) => println!("Failed to save summary: {}", e),
+    }
+}
+
+fn share_past_game(user_id: i64) -> Result<(), Box<dyn Error>> {
+    let history = DB::get_motus_history(user_id)?;
+    if history.is_empty() {
+        println!("No Motus game recorded yet.");
+        return Ok(());
+    }
+
+    println!("Your last Motus games:");
+    for (i, entry) in history.iter().enumerate() {
+        let result = if entry.won { "won" } else { "lost" };
+        println!("{}. {} - {} - {} in {} attempts", i + 1, entry.played_at, entry.theme, result, entry.attempts_used);
+    }
+
+    let choice = utils::get_valid_choice() as usize;
+    match history.get(choice.wrapping_sub(1)) {
+        Some(entry) => {
+            let summary = share_summary(&entry.theme, entry.attempts_used, entry.won, &entry.grid);
+            println!("\n{}\n", summary);
+            offer_to_save_summary(entry.id, &summary);
+        }
+        None => println!("Invalid choice."),
+    }
+
+    Ok(())
+}
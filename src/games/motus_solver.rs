@@ -0,0 +1,76 @@
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LetterFeedback {
+    Correct,
+    Misplaced,
+    Absent,
+}
+
+// meme regle que l'affichage du Motus : une lettre est mal placee
+// des qu'elle apparait ailleurs dans le mot secret
+pub fn evaluate_guess(secret: &str, guess: &str) -> Vec<LetterFeedback> {
+    let secret_chars: Vec<char> = secret.chars().collect();
+
+    guess
+        .chars()
+        .enumerate()
+        .map(|(index, guess_char)| {
+            if secret_chars.get(index) == Some(&guess_char) {
+                LetterFeedback::Correct
+            } else if secret_chars.contains(&guess_char) {
+                LetterFeedback::Misplaced
+            } else {
+                LetterFeedback::Absent
+            }
+        })
+        .collect()
+}
+
+pub struct Solver {
+    candidates: Vec<String>,
+}
+
+impl Solver {
+    pub fn new(words: &[String], word_length: usize) -> Self {
+        let mut candidates: Vec<String> = words
+            .iter()
+            .filter(|word| word.chars().count() == word_length)
+            .cloned()
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        Solver { candidates }
+    }
+
+    pub fn apply_feedback(&mut self, guess: &str, feedback: &[LetterFeedback]) {
+        self.candidates
+            .retain(|candidate| evaluate_guess(candidate, guess) == feedback);
+    }
+
+    pub fn apply_revealed_letter(&mut self, index: usize, letter: char) {
+        self.candidates
+            .retain(|candidate| candidate.chars().nth(index) == Some(letter));
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    // choisit le candidat qui laisse le moins de mots possibles dans le pire des cas
+    pub fn suggest(&self) -> Option<&str> {
+        self.candidates
+            .iter()
+            .min_by_key(|guess| {
+                let mut buckets: Vec<(Vec<LetterFeedback>, usize)> = Vec::new();
+                for candidate in &self.candidates {
+                    let feedback = evaluate_guess(candidate, guess);
+                    match buckets.iter_mut().find(|(pattern, _)| *pattern == feedback) {
+                        Some((_, count)) => *count += 1,
+                        None => buckets.push((feedback, 1)),
+                    }
+                }
+                buckets.iter().map(|(_, count)| *count).max().unwrap_or(0)
+            })
+            .map(|word| word.as_str())
+    }
+}
@@ -1,161 +1,561 @@
 use std::error::Error;
 use rand::Rng;
-use std::time::Instant;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
 
 use crate::db::DbManager as DB;
 use crate::User;
 use crate::utils;
+use crate::input::{self, Key, RawMode};
+use crate::games::reflex_stats::{self, ReactionStats};
+use crossterm::style::{Color, Stylize};
+use serde::Serialize;
 
 pub const GAME_NAME: &str = "reflex";
+const MIN_FOREPERIOD_MS: u64 = 1000;
+const MAX_FOREPERIOD_MS: u64 = 3000;
+const FALSE_START_PENALTY: i32 = 1;
+// cible utilisee apres la phase 1 si aucun essai valide en phase 1
+const DEFAULT_TARGET_REACTION_TIME: u128 = 1000;
+const MIN_TARGET_REACTION_TIME: u128 = 150;
+const MAX_TARGET_REACTION_TIME: u128 = 3000;
+const ADAPTIVE_STEP_PERCENT: u128 = 10;
+const TRIALS_PER_PHASE: u32 = 10;
+const NO_GO_WORD: &str = "STOP";
+const NO_GO_PROBABILITY: f64 = 0.25;
+// temps pendant lequel il ne faut pas appuyer sur un essai "no-go"
+const NO_GO_WINDOW_MS: u64 = 1500;
 
+const KEY_BINDINGS_SETTING: &str = "reflex_key_bindings";
+
+#[derive(Clone, Copy, PartialEq)]
+enum KeyBindings {
+    Azerty,
+    Qwerty,
+    Arrows,
+}
+
+impl KeyBindings {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(KeyBindings::Azerty),
+            2 => Some(KeyBindings::Qwerty),
+            3 => Some(KeyBindings::Arrows),
+            _ => None,
+        }
+    }
+
+    fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "azerty" => Some(KeyBindings::Azerty),
+            "qwerty" => Some(KeyBindings::Qwerty),
+            "arrows" => Some(KeyBindings::Arrows),
+            _ => None,
+        }
+    }
+
+    fn as_setting(&self) -> &'static str {
+        match self {
+            KeyBindings::Azerty => "azerty",
+            KeyBindings::Qwerty => "qwerty",
+            KeyBindings::Arrows => "arrows",
+        }
+    }
+
+    fn left_key(&self) -> Key {
+        match self {
+            KeyBindings::Azerty => Key::Char('q'),
+            KeyBindings::Qwerty => Key::Char('a'),
+            KeyBindings::Arrows => Key::Left,
+        }
+    }
+
+    fn right_key(&self) -> Key {
+        match self {
+            KeyBindings::Azerty | KeyBindings::Qwerty => Key::Char('d'),
+            KeyBindings::Arrows => Key::Right,
+        }
+    }
+
+    fn left_label(&self) -> &'static str {
+        match self {
+            KeyBindings::Azerty => "'q'",
+            KeyBindings::Qwerty => "'a'",
+            KeyBindings::Arrows => "left arrow",
+        }
+    }
+
+    fn right_label(&self) -> &'static str {
+        match self {
+            KeyBindings::Azerty | KeyBindings::Qwerty => "'d'",
+            KeyBindings::Arrows => "right arrow",
+        }
+    }
+
+    fn load(user_id: i64) -> Self {
+        match DB::get_user_setting(user_id, KEY_BINDINGS_SETTING) {
+            Ok(Some(value)) => KeyBindings::from_setting(&value).unwrap_or(KeyBindings::Azerty),
+            Ok(None) => KeyBindings::Azerty,
+            Err(e) => {
+                println!("Failed to load key bindings: {}", e);
+                KeyBindings::Azerty
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, PartialEq)]
 enum GamePhase{
     Phase1,
     Phase2,
+    Stroop,
+    GoNoGo,
+    Arrows,
+}
+
+impl GamePhase {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(GamePhase::Phase1),
+            2 => Some(GamePhase::Phase2),
+            3 => Some(GamePhase::Stroop),
+            4 => Some(GamePhase::GoNoGo),
+            5 => Some(GamePhase::Arrows),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            GamePhase::Phase1 => "Phase 1 - left/right words",
+            GamePhase::Phase2 => "Phase 2 - inverted uppercase words",
+            GamePhase::Stroop => "Stroop - ink colors",
+            GamePhase::GoNoGo => "Go/No-go",
+            GamePhase::Arrows => "Arrows with distractors",
+        }
+    }
+
+    fn next_stimulus(&self) -> Stimulus {
+        let mut rng = rand::rng();
+        let direction = if rng.random_bool(0.5) { Direction::Left } else { Direction::Right };
+
+        match self {
+            GamePhase::Phase1 => Stimulus::word(direction_word(direction), Some(direction)),
+            GamePhase::Phase2 => {
+                // en majuscules il faut appuyer sur la touche opposee
+                if rng.random_bool(0.5) {
+                    Stimulus::word(direction_word(direction), Some(direction))
+                } else {
+                    Stimulus::word(&direction_word(opposite(direction)).to_uppercase(), Some(direction))
+                }
+            }
+            GamePhase::Stroop => {
+                // seule la couleur de l'encre compte, pas le mot ecrit
+                let word = if rng.random_bool(0.5) { "RED" } else { "BLUE" };
+                let ink = match direction {
+                    Direction::Left => Color::Red,
+                    Direction::Right => Color::Blue,
+                };
+                Stimulus {
+                    text: word.to_string(),
+                    color: Some(ink),
+                    expected: Some(direction),
+                }
+            }
+            GamePhase::GoNoGo => {
+                if rng.random_bool(NO_GO_PROBABILITY) {
+                    Stimulus::word(NO_GO_WORD, None)
+                } else {
+                    Stimulus::word(direction_word(direction), Some(direction))
+                }
+            }
+            GamePhase::Arrows => {
+                let (target, distractor) = match direction {
+                    Direction::Left => ('<', '>'),
+                    Direction::Right => ('>', '<'),
+                };
+                let flanker = if rng.random_bool(0.5) { target } else { distractor };
+                let text = format!("{0}{0}{1}{0}{0}", flanker, target);
+                Stimulus::word(&text, Some(direction))
+            }
+        }
+    }
+}
+
+fn direction_word(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Left => "left",
+        Direction::Right => "right",
+    }
+}
+
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
+
+struct Stimulus {
+    text: String,
+    color: Option<Color>,
+    // None : essai "no-go", il ne faut pas appuyer
+    expected: Option<Direction>,
+}
+
+impl Stimulus {
+    fn word(text: &str, expected: Option<Direction>) -> Self {
+        Stimulus {
+            text: text.to_string(),
+            color: None,
+            expected,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TargetMode {
+    Fixed,
+    Adaptive,
 }
+
 struct Game {
-    words_phase1: Vec<&'static str>,
-    words_phase2: Vec<&'static str>,
+    key_bindings: KeyBindings,
+    target_mode: TargetMode,
     score:i32,
-    total_reaction_time : u64,
     phase1_reaction_times: Vec<u128>,
-    target_reaction_time : u128
+    target_reaction_time : u128,
+    false_starts: u32,
+    trials: Vec<Trial>,
+}
+
+struct Trial {
+    phase: GamePhase,
+    reaction_time: Option<u128>,
+    correct: bool,
+    false_start: bool,
+}
+
+#[derive(Serialize)]
+struct PhaseSummary {
+    phase: String,
+    trials: usize,
+    correct: usize,
+    accuracy: f64,
+    false_starts: usize,
+    reaction_times: Option<ReactionStats>,
+}
+
+#[derive(Serialize)]
+struct SessionSummary {
+    score: i32,
+    false_starts: u32,
+    phases: Vec<PhaseSummary>,
+    reaction_times: Option<ReactionStats>,
+    inversion_cost_ms: Option<f64>,
 }
 
 impl Game {
-    fn new() -> Game {
+    fn new(key_bindings: KeyBindings, target_mode: TargetMode) -> Game {
         Game {
-            words_phase1: vec!["left", "right"],
-            words_phase2: vec!["left", "right", "LEFT", "RIGHT"],
+            key_bindings,
+            target_mode,
             score: 0,
-            total_reaction_time: 0,
             phase1_reaction_times: Vec::new(),
             target_reaction_time: 0,
+            false_starts: 0,
+            trials: Vec::new(),
         }
     }
 
-    fn run(&mut self, phase: &GamePhase){
-        self.display_instruction(phase);
-        self.run_phase(phase);
+    fn run(&mut self, phase: &GamePhase) -> io::Result<()> {
+        self.display_instruction(phase)?;
+        self.run_phase(phase)
     }
 
-    fn display_instruction(&mut self, phase: &GamePhase) {
+    fn display_instruction(&mut self, phase: &GamePhase) -> io::Result<()> {
+        let left = self.key_bindings.left_label();
+        let right = self.key_bindings.right_label();
+
+        println!("{}\n", phase.as_str());
         match phase {
             GamePhase::Phase1 => {
-                println!("Press the 'q' or 'd' key as quickly as possible based respectively on the words 'left' or 'right' displayed.");
-                println!("Then, press 'Enter' to validate your choice.");
+                println!(
+                    "Press the {} or {} key as quickly as possible based respectively on the words 'left' or 'right' displayed.",
+                    left,
+                    right
+                );
             }
             GamePhase::Phase2 => {
-
-                self.target_reaction_time = self.phase1_reaction_times.iter().sum::<u128>() / self.phase1_reaction_times.len() as u128;
-                self.target_reaction_time += self.target_reaction_time / 2;
-                println!("Now, if the words LEFT and RIGHT are in uppercase, you must press the opposite key.");
-                println!("As before, you must press 'Enter' to validate your choice.");
-                println!("Based on your scores from Phase 1, you must respond in less than {} ms to earn points.", self.target_reaction_time);
-
+                println!("Press {} for 'left' and {} for 'right'.", left, right);
+                println!("But if the words LEFT and RIGHT are in uppercase, you must press the opposite key.");
+            }
+            GamePhase::Stroop => {
+                println!("The words RED and BLUE are displayed in red or blue ink.");
+                println!("Ignore the word: press {} if the ink is red and {} if the ink is blue.", left, right);
+            }
+            GamePhase::GoNoGo => {
+                println!("Press {} for 'left' and {} for 'right'.", left, right);
+                println!("But when {} is displayed, do not press anything!", NO_GO_WORD);
+            }
+            GamePhase::Arrows => {
+                println!("Five arrows are displayed: only the middle one matters.");
+                println!("Press {} if it points left and {} if it points right.", left, right);
             }
         }
-        wait_for_enter();
-    }
+        println!("No need to press 'Enter': the first key you press is your answer.");
+        println!("Wait for the stimulus: pressing a key while '+' is shown is a false start and costs a point.");
 
-    fn run_phase(&mut self, phase: &GamePhase) {
-        let mut rng = rand::rng();
-        match phase {
-            GamePhase::Phase1 => {
-                for _ in 0..10 {
-                    let randow_word = self.words_phase1[rng.random_range(0..self.words_phase1.len())];
-                    let reaction_time = self.display_word(randow_word, phase);
-                    self.total_reaction_time += reaction_time;
-                    wait_for_enter();
-                }
+        if *phase != GamePhase::Phase1 {
+            // en mode adaptatif la cible n'est calculee qu'une fois puis evolue a chaque essai
+            if self.target_mode == TargetMode::Fixed || self.target_reaction_time == 0 {
+                self.update_target_reaction_time();
             }
-            GamePhase::Phase2 => {
-                for _ in 0..10 {
-                    let randow_word = self.words_phase2[rng.random_range(0..self.words_phase2.len())];
-                    let reaction_time = self.display_word(randow_word, phase);
-                    self.total_reaction_time += reaction_time;
-                    wait_for_enter();
-                }
+            println!("You must respond in less than {} ms to earn points.", self.target_reaction_time);
+            if self.target_mode == TargetMode::Adaptive {
+                println!("Adaptive mode: the target tightens when you answer in time and loosens when you miss.");
             }
         }
+        wait_for_enter()
     }
 
-    fn display_word(&mut self, word: &str, phase: &GamePhase) -> u64{
-        let start_time = Instant::now();
-        utils::clear_terminal();
-        println!("{}", word);
-        let key = get_key_pressed();
-        let reaction_time = start_time.elapsed().as_millis();
-
-        if self.is_correct_key(word, key){
-            match phase {
-                GamePhase::Phase1 => {
-                    println!("Correct! Reaction time: {} ms", reaction_time);
-                    self.score += 1;
-                    self.phase1_reaction_times.push(reaction_time);
-                }
-                GamePhase::Phase2 => {
-                    if reaction_time <= self.target_reaction_time {
-                        println!("Correct! Reaction time: {} ms", reaction_time);
-                        self.score += 1;
-                    } else {
-                        println!("Correct but too slow! Reaction time: {} ms", reaction_time);
+    fn update_target_reaction_time(&mut self) {
+        // les faux departs ne sont pas comptes dans la moyenne
+        self.target_reaction_time = if self.phase1_reaction_times.is_empty() {
+            DEFAULT_TARGET_REACTION_TIME
+        } else {
+            let average = self.phase1_reaction_times.iter().sum::<u128>() / self.phase1_reaction_times.len() as u128;
+            average + average / 2
+        };
+    }
+
+    fn adapt_target(&mut self, phase: &GamePhase, success: bool) {
+        if self.target_mode != TargetMode::Adaptive || *phase == GamePhase::Phase1 {
+            return;
+        }
+
+        let step = self.target_reaction_time * ADAPTIVE_STEP_PERCENT / 100;
+        self.target_reaction_time = if success {
+            self.target_reaction_time.saturating_sub(step)
+        } else {
+            self.target_reaction_time + step
+        }
+        .clamp(MIN_TARGET_REACTION_TIME, MAX_TARGET_REACTION_TIME);
+        println!("New target: {} ms", self.target_reaction_time);
+    }
+
+    fn run_phase(&mut self, phase: &GamePhase) -> io::Result<()> {
+        for _ in 0..TRIALS_PER_PHASE {
+            let stimulus = phase.next_stimulus();
+            self.play_trial(&stimulus, phase)?;
+            wait_for_enter()?;
+        }
+        Ok(())
+    }
+
+    fn play_trial(&mut self, stimulus: &Stimulus, phase: &GamePhase) -> io::Result<()> {
+        let mut trial = Trial {
+            phase: *phase,
+            reaction_time: None,
+            correct: false,
+            false_start: false,
+        };
+
+        match present_stimulus(stimulus)? {
+            Response::FalseStart => {
+                self.false_starts += 1;
+                self.score -= FALSE_START_PENALTY;
+                trial.false_start = true;
+                println!("Too early! False start (-{} point).", FALSE_START_PENALTY);
+            }
+            Response::Withheld => {
+                println!("Well done, you held back!");
+                self.score += 1;
+                trial.correct = true;
+            }
+            Response::Key(key, reaction_time) => {
+                trial.reaction_time = Some(reaction_time);
+                match stimulus.expected {
+                    None => {
+                        println!("You should not have pressed anything!");
+                        self.adapt_target(phase, false);
+                    }
+                    Some(expected) if self.is_correct_key(expected, key) => {
+                        trial.correct = true;
+                        if *phase == GamePhase::Phase1 {
+                            println!("Correct! Reaction time: {} ms", reaction_time);
+                            self.score += 1;
+                            self.phase1_reaction_times.push(reaction_time);
+                        } else if reaction_time <= self.target_reaction_time {
+                            println!("Correct! Reaction time: {} ms", reaction_time);
+                            self.score += 1;
+                            self.adapt_target(phase, true);
+                        } else {
+                            println!("Correct but too slow! Reaction time: {} ms", reaction_time);
+                            self.adapt_target(phase, false);
+                        }
+                    }
+                    Some(_) => {
+                        println!("Wrong key!");
+                        self.adapt_target(phase, false);
                     }
                 }
             }
-        } else {
-            println!("Wrong key!");
         }
 
-        reaction_time as u64
+        self.trials.push(trial);
+        Ok(())
+    }
+
+    // temps de reaction des bonnes reponses uniquement
+    fn correct_reaction_times(&self, phase: Option<GamePhase>) -> Vec<u128> {
+        self.trials
+            .iter()
+            .filter(|trial| trial.correct && phase.is_none_or(|phase| trial.phase == phase))
+            .filter_map(|trial| trial.reaction_time)
+            .collect()
+    }
+
+    fn summary(&self, phases: &[GamePhase]) -> SessionSummary {
+        let phase_summaries = phases
+            .iter()
+            .filter(|phase| self.trials.iter().any(|trial| trial.phase == **phase))
+            .map(|phase| {
+                let trials: Vec<&Trial> = self.trials.iter().filter(|trial| trial.phase == *phase).collect();
+                let correct = trials.iter().filter(|trial| trial.correct).count();
+                PhaseSummary {
+                    phase: phase.as_str().to_string(),
+                    trials: trials.len(),
+                    correct,
+                    accuracy: correct as f64 * 100.0 / trials.len() as f64,
+                    false_starts: trials.iter().filter(|trial| trial.false_start).count(),
+                    reaction_times: ReactionStats::from_times(&self.correct_reaction_times(Some(*phase))),
+                }
+            })
+            .collect();
+
+        // cout de la regle d'inversion : ralentissement moyen entre phase 1 et phase 2
+        let phase1 = ReactionStats::from_times(&self.correct_reaction_times(Some(GamePhase::Phase1)));
+        let phase2 = ReactionStats::from_times(&self.correct_reaction_times(Some(GamePhase::Phase2)));
+        let inversion_cost_ms = match (phase1, phase2) {
+            (Some(phase1), Some(phase2)) => Some(phase2.mean - phase1.mean),
+            _ => None,
+        };
 
+        SessionSummary {
+            score: self.score,
+            false_starts: self.false_starts,
+            phases: phase_summaries,
+            reaction_times: ReactionStats::from_times(&self.correct_reaction_times(None)),
+            inversion_cost_ms,
+        }
     }
 
-    fn is_correct_key(&self, word: &str, key: char) -> bool {
-        match key {
-            'd' if word == "right" => true,
-            'q' if word == "left" => true,
-            'd' if word == "LEFT" => true,
-            'q' if word == "RIGHT" => true,
-            _ => false,
+    fn is_correct_key(&self, expected: Direction, key: Key) -> bool {
+        match expected {
+            Direction::Left => key == self.key_bindings.left_key(),
+            Direction::Right => key == self.key_bindings.right_key(),
         }
     }
 }
 
-fn wait_for_enter() {
-    use std::io::Write;
+enum Response {
+    FalseStart,
+    Withheld,
+    Key(Key, u128),
+}
+
+fn present_stimulus(stimulus: &Stimulus) -> io::Result<Response> {
+    let _raw_mode = RawMode::enable()?;
+    input::discard_pending_keys()?;
+
+    utils::clear_terminal();
+    print!("+\r\n");
+    io::stdout().flush()?;
+    if key_pressed_during_foreperiod()? {
+        return Ok(Response::FalseStart);
+    }
+
+    utils::clear_terminal();
+    match stimulus.color {
+        Some(color) => print!("{}\r\n", stimulus.text.as_str().with(color).bold()),
+        None => print!("{}\r\n", stimulus.text),
+    }
+    io::stdout().flush()?;
+    // le chrono ne demarre qu'une fois le stimulus affiche
+    let start_time = Instant::now();
+    let key_press = match stimulus.expected {
+        Some(_) => Some(input::read_key()?),
+        None => input::poll_key(Duration::from_millis(NO_GO_WINDOW_MS))?,
+    };
+
+    Ok(match key_press {
+        Some((key, pressed_at)) => Response::Key(key, pressed_at.duration_since(start_time).as_millis()),
+        None => Response::Withheld,
+    })
+}
+
+fn wait_for_enter() -> io::Result<()> {
     loop {
         println!("Press 'Enter' to continue...");
-        std::io::stdout().flush().unwrap();
+        io::stdout().flush()?;
         let mut input = String::new();
-        std::io::stdin().read_line(&mut input).expect("Failed to read line");
+        io::stdin().read_line(&mut input)?;
         
         let input = input.trim();
         if !input.is_empty() {
             continue;
         } 
-        break;
+        return Ok(());
     }
 }
 
-fn get_key_pressed() -> char {
-    let input = utils::get_valid_input("Press a key:");
-    input.chars().next().unwrap_or('\n')
+// delai aleatoire avant chaque mot pour que le joueur ne puisse pas l'anticiper,
+// toute touche pressee pendant ce delai est un faux depart
+fn key_pressed_during_foreperiod() -> io::Result<bool> {
+    let mut rng = rand::rng();
+    let delay = rng.random_range(MIN_FOREPERIOD_MS..=MAX_FOREPERIOD_MS);
+    Ok(input::poll_key(Duration::from_millis(delay))?.is_some())
 }
 
 pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
     let user_id = user.id;
     let username = user.username.clone();
-    let mut game = Game::new();
+    let key_bindings = KeyBindings::load(user_id);
     println!("Welcome on the Reflex Game !\n");
+    let phases = select_phases();
+    let target_mode = select_target_mode();
 
     loop{
-        game.run(&GamePhase::Phase1);
+        // chaque partie repart de zero
+        let mut game = Game::new(key_bindings, target_mode);
+        for phase in &phases {
+            utils::clear_terminal();
+            game.run(phase)?;
+        }
+
         utils::clear_terminal();
-        game.run(&GamePhase::Phase2);
+        let summary = game.summary(&phases);
+        display_results(&summary, &game.correct_reaction_times(None));
 
-        match DB::add_game_score(GAME_NAME, user_id, &username, game.score) {
+        // seules les sessions classiques a objectif fixe sont comparables entre elles
+        if is_ranked(&phases, target_mode) {
+            match DB::add_game_score(GAME_NAME, user_id, &username, game.score) {
+                Ok(_) => {}
+                Err(e) => println!("Failed to save score: {}", e),
+            }
+        } else {
+            println!("Only classic sessions with a fixed target count for the leaderboard.");
+        }
+        match DB::add_reflex_session(user_id, game.score, &serde_json::to_string(&summary)?) {
             Ok(_) => {}
-            Err(e) => println!("Failed to save score: {}", e),
+            Err(e) => println!("Failed to save session statistics: {}", e),
         }
 
         match utils::play_again() {
@@ -167,4 +567,99 @@ pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+fn is_ranked(phases: &[GamePhase], target_mode: TargetMode) -> bool {
+    phases == [GamePhase::Phase1, GamePhase::Phase2] && target_mode == TargetMode::Fixed
+}
+
+fn display_results(summary: &SessionSummary, reaction_times: &[u128]) {
+    println!("Results\n");
+    println!("Score: {}", summary.score);
+    println!("False starts: {} (-{} point each)\n", summary.false_starts, FALSE_START_PENALTY);
+
+    for phase in &summary.phases {
+        println!("{}", phase.phase);
+        println!("  Accuracy: {}/{} ({:.0}%), false starts: {}", phase.correct, phase.trials, phase.accuracy, phase.false_starts);
+        match &phase.reaction_times {
+            Some(stats) => println!("  Reaction time: {}", stats.describe()),
+            None => println!("  Reaction time: no correct answer"),
+        }
+    }
+
+    if let Some(stats) = &summary.reaction_times {
+        println!("\nOverall reaction time: {}", stats.describe());
+    }
+    if let Some(cost) = summary.inversion_cost_ms {
+        println!("Slowdown from Phase 1 to Phase 2 (cost of the inversion rule): {:+.0} ms", cost);
+    }
+
+    if !reaction_times.is_empty() {
+        println!("\nReaction time distribution:");
+        println!("{}", reflex_stats::histogram(reaction_times));
+    }
+    println!();
+}
+
+fn select_target_mode() -> TargetMode {
+    println!("Choose how the speed target is set:");
+    println!("1. Fixed (Phase 1 average + 50%)");
+    println!("2. Adaptive (adjusted after every answer)");
+
+    match utils::get_valid_choice() {
+        2 => TargetMode::Adaptive,
+        _ => TargetMode::Fixed,
+    }
+}
+
+fn select_phases() -> Vec<GamePhase> {
+    let classic = vec![GamePhase::Phase1, GamePhase::Phase2];
+
+    println!("Choose the phases for this session:");
+    println!("1. Classic (Phase 1 + Phase 2)");
+    println!("2. All phases");
+    println!("3. Custom");
+
+    match utils::get_valid_choice() {
+        2 => (1..=5).filter_map(GamePhase::from_choice).collect(),
+        3 => {
+            for choice in 1..=5 {
+                if let Some(phase) = GamePhase::from_choice(choice) {
+                    println!("{}. {}", choice, phase.as_str());
+                }
+            }
+            let input = utils::get_valid_input("Enter the phases to play in order (e.g. 1,3,5):");
+            let phases: Vec<GamePhase> = input
+                .split(',')
+                .filter_map(|choice| choice.trim().parse().ok())
+                .filter_map(GamePhase::from_choice)
+                .collect();
+            if phases.is_empty() {
+                println!("No valid phase selected, playing the classic session.");
+                classic
+            } else {
+                phases
+            }
+        }
+        _ => classic,
+    }
+}
+
+pub fn choose_key_bindings(user: &User) -> Result<(), Box<dyn Error>> {
+    let current = KeyBindings::load(user.id);
+    println!("Current Reflex keys: {} for left, {} for right", current.left_label(), current.right_label());
+    println!("Choose your key bindings:");
+    println!("1. AZERTY ('q' for left, 'd' for right)");
+    println!("2. QWERTY ('a' for left, 'd' for right)");
+    println!("3. Arrow keys");
+
+    match KeyBindings::from_choice(utils::get_valid_choice()) {
+        Some(key_bindings) => {
+            DB::set_user_setting(user.id, KEY_BINDINGS_SETTING, key_bindings.as_setting())?;
+            println!("Key bindings saved.");
+        }
+        None => println!("Invalid choice, key bindings unchanged."),
+    }
+
+    Ok(())
+}
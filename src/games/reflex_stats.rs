@@ -0,0 +1,98 @@
+use serde::Serialize;
+
+const HISTOGRAM_BUCKET_MS: u128 = 100;
+const HISTOGRAM_MAX_WIDTH: usize = 40;
+// les temps au dela sont regroupes dans une derniere ligne
+const HISTOGRAM_CEILING_MS: u128 = 2000;
+
+#[derive(Serialize)]
+pub struct ReactionStats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p10: f64,
+    pub p90: f64,
+    pub std_dev: f64,
+    pub min: u128,
+    pub max: u128,
+}
+
+impl ReactionStats {
+    pub fn from_times(times: &[u128]) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+
+        let mut sorted = times.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let mean = sorted.iter().sum::<u128>() as f64 / count as f64;
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) as f64 / 2.0
+        } else {
+            sorted[count / 2] as f64
+        };
+        let variance = sorted
+            .iter()
+            .map(|&time| (time as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Some(ReactionStats {
+            count,
+            mean,
+            median,
+            p10: percentile(&sorted, 10.0),
+            p90: percentile(&sorted, 90.0),
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            max: sorted[count - 1],
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "mean {:.0} ms, median {:.0} ms, p10 {:.0} ms, p90 {:.0} ms, SD {:.0} ms, min {} ms, max {} ms",
+            self.mean, self.median, self.p10, self.p90, self.std_dev, self.min, self.max
+        )
+    }
+}
+
+// interpolation lineaire entre les deux rangs les plus proches
+fn percentile(sorted: &[u128], percent: f64) -> f64 {
+    let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] as f64 + (sorted[upper] as f64 - sorted[lower] as f64) * (rank - lower as f64)
+}
+
+// une ligne par tranche de 100 ms jusqu'a 2 s, puis une ligne pour tout le reste ;
+// la barre la plus longue fait 40 caracteres
+pub fn histogram(times: &[u128]) -> String {
+    let (Some(&min), Some(&max)) = (times.iter().min(), times.iter().max()) else {
+        return String::new();
+    };
+
+    let first_bucket = min.min(HISTOGRAM_CEILING_MS) / HISTOGRAM_BUCKET_MS;
+    let last_bucket = max.min(HISTOGRAM_CEILING_MS) / HISTOGRAM_BUCKET_MS;
+    let mut counts = vec![0usize; (last_bucket - first_bucket + 1) as usize];
+    for &time in times {
+        counts[(time.min(HISTOGRAM_CEILING_MS) / HISTOGRAM_BUCKET_MS - first_bucket) as usize] += 1;
+    }
+
+    let highest = counts.iter().copied().max().unwrap_or(1);
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let start = (first_bucket + i as u128) * HISTOGRAM_BUCKET_MS;
+            let width = count * HISTOGRAM_MAX_WIDTH / highest;
+            let label = if start >= HISTOGRAM_CEILING_MS {
+                format!("{:>11} ms", format!(">= {}", start))
+            } else {
+                format!("{:>5}-{:>5} ms", start, start + HISTOGRAM_BUCKET_MS - 1)
+            };
+            format!("{} | {} {}", label, "#".repeat(width), count)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,192 @@
+use std::error::Error;
+use rand::Rng;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::utils;
+use crate::input::{self, RawMode};
+use crossterm::style::{Color, Stylize};
+
+const PAUSE_BETWEEN_SYMBOLS_MS: u64 = 250;
+const COLORS: [(char, &str, Color); 4] = [
+    ('R', "RED", Color::Red),
+    ('G', "GREEN", Color::Green),
+    ('B', "BLUE", Color::Blue),
+    ('Y', "YELLOW", Color::Yellow),
+];
+const DIGITS: [char; 9] = ['1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+#[derive(Clone, Copy)]
+enum Symbols {
+    Colors,
+    Digits,
+}
+
+impl Symbols {
+    fn count(&self) -> usize {
+        match self {
+            Symbols::Colors => COLORS.len(),
+            Symbols::Digits => DIGITS.len(),
+        }
+    }
+
+    fn symbol(&self, index: usize) -> char {
+        match self {
+            Symbols::Colors => COLORS[index].0,
+            Symbols::Digits => DIGITS[index],
+        }
+    }
+
+    fn display(&self, index: usize) -> String {
+        match self {
+            Symbols::Colors => {
+                let (_, name, color) = COLORS[index];
+                format!("{}", name.with(color).bold())
+            }
+            Symbols::Digits => format!("{}", DIGITS[index].bold()),
+        }
+    }
+
+    fn legend(&self) -> String {
+        match self {
+            Symbols::Colors => COLORS
+                .iter()
+                .map(|(symbol, name, _)| format!("{} = {}", symbol, name.to_lowercase()))
+                .collect::<Vec<String>>()
+                .join(", "),
+            Symbols::Digits => "digits 1 to 9".to_string(),
+        }
+    }
+
+    fn parse(&self, input: &str) -> Vec<usize> {
+        input
+            .to_uppercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| (0..self.count()).find(|&i| self.symbol(i) == c).unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Speed {
+    Slow,
+    Normal,
+    Fast,
+}
+
+impl Speed {
+    fn display_ms(&self) -> u64 {
+        match self {
+            Speed::Slow => 1200,
+            Speed::Normal => 800,
+            Speed::Fast => 400,
+        }
+    }
+
+    // une table par vitesse, les scores ne sont comparables qu'a vitesse egale
+    fn table_name(&self) -> &'static str {
+        match self {
+            Speed::Slow => "simon_slow",
+            Speed::Normal => "simon_normal",
+            Speed::Fast => "simon_fast",
+        }
+    }
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Simon Game !\n");
+
+    println!("Play with:");
+    println!("1. Colors");
+    println!("2. Digits");
+    let symbols = match utils::get_valid_choice() {
+        2 => Symbols::Digits,
+        _ => Symbols::Colors,
+    };
+
+    println!("Select the speed:");
+    println!("1. Slow");
+    println!("2. Normal");
+    println!("3. Fast");
+    let speed = match utils::get_valid_choice() {
+        1 => Speed::Slow,
+        3 => Speed::Fast,
+        _ => Speed::Normal,
+    };
+
+    println!("\nWatch the sequence, then type it back ({}).", symbols.legend());
+    println!("Each level adds one element to the sequence.");
+    println!("Press 'Enter' to start...");
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).expect("Failed to read line");
+
+    loop {
+        let score = play_round(symbols, speed)?;
+        println!("You reproduced a sequence of {} elements.", score);
+        println!("Your score is: {}", score);
+        match DB::add_game_score(speed.table_name(), user_id, &username, score as i32) {
+            Ok(_) => {},
+            Err(e) => println!("Failed to save score: {}", e),
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// renvoie la longueur de la plus longue sequence reproduite
+fn play_round(symbols: Symbols, speed: Speed) -> Result<usize, Box<dyn Error>> {
+    let mut rng = rand::rng();
+    let mut sequence: Vec<usize> = Vec::new();
+
+    loop {
+        sequence.push(rng.random_range(0..symbols.count()));
+        show_sequence(&sequence, symbols, speed)?;
+
+        let answer = symbols.parse(&utils::get_valid_input(&format!("Level {} - your sequence:", sequence.len())));
+        if answer != sequence {
+            let expected: String = sequence.iter().map(|&i| symbols.symbol(i)).collect();
+            println!("Wrong! The sequence was: {}", expected);
+            return Ok(sequence.len() - 1);
+        }
+        println!("Correct!");
+        thread::sleep(Duration::from_millis(speed.display_ms()));
+    }
+}
+
+fn show_sequence(sequence: &[usize], symbols: Symbols, speed: Speed) -> Result<(), Box<dyn Error>> {
+    // pas d'echo des touches pressees pendant l'affichage
+    let raw_mode = RawMode::enable()?;
+    let mut stdout = std::io::stdout();
+
+    for (position, &index) in sequence.iter().enumerate() {
+        utils::clear_terminal();
+        print!("Level {} - {}/{}\r\n\r\n", sequence.len(), position + 1, sequence.len());
+        print!("    {}\r\n", symbols.display(index));
+        stdout.flush()?;
+        thread::sleep(Duration::from_millis(speed.display_ms()));
+
+        // efface le symbole pour distinguer deux symboles identiques consecutifs
+        utils::clear_terminal();
+        thread::sleep(Duration::from_millis(PAUSE_BETWEEN_SYMBOLS_MS));
+    }
+
+    input::discard_pending_keys()?;
+    drop(raw_mode);
+    Ok(())
+}
@@ -0,0 +1,434 @@
+use std::error::Error;
+use std::time::Instant;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crossterm::style::Stylize;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::utils;
+
+// chaque indice donne coute du temps
+const HINT_PENALTY_SECONDS: u64 = 30;
+
+type Grid = [u8; 81];
+
+#[derive(Clone, Copy)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    // nombre de chiffres donnes vise par le generateur
+    fn clues(&self) -> usize {
+        match self {
+            Difficulty::Easy => 40,
+            Difficulty::Medium => 32,
+            Difficulty::Hard => 26,
+        }
+    }
+
+    fn table_name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "sudoku_easy",
+            Difficulty::Medium => "sudoku_medium",
+            Difficulty::Hard => "sudoku_hard",
+        }
+    }
+}
+
+enum Command {
+    Place(usize, u8),
+    Clear(usize),
+    Note(usize, Vec<u8>),
+    Hint,
+    Quit,
+}
+
+fn can_place(grid: &Grid, index: usize, digit: u8) -> bool {
+    let (row, col) = (index / 9, index % 9);
+    let box_start = (row / 3) * 27 + (col / 3) * 3;
+    (0..9).all(|i| {
+        let box_cell = box_start + (i / 3) * 9 + i % 3;
+        (row * 9 + i == index || grid[row * 9 + i] != digit)
+            && (i * 9 + col == index || grid[i * 9 + col] != digit)
+            && (box_cell == index || grid[box_cell] != digit)
+    })
+}
+
+// backtracking sur la case vide qui a le moins de candidats,
+// s'arrete des que `limit` solutions ont ete trouvees
+fn count_solutions(grid: &mut Grid, limit: usize, first_solution: &mut Option<Grid>) -> usize {
+    let mut best: Option<(usize, Vec<u8>)> = None;
+    for index in 0..81 {
+        if grid[index] != 0 {
+            continue;
+        }
+        let candidates: Vec<u8> = (1..=9).filter(|&digit| can_place(grid, index, digit)).collect();
+        if candidates.is_empty() {
+            return 0;
+        }
+        if best.as_ref().is_none_or(|(_, best_candidates)| candidates.len() < best_candidates.len()) {
+            best = Some((index, candidates));
+        }
+    }
+
+    let Some((index, candidates)) = best else {
+        first_solution.get_or_insert(*grid);
+        return 1;
+    };
+
+    let mut count = 0;
+    for digit in candidates {
+        grid[index] = digit;
+        count += count_solutions(grid, limit - count, first_solution);
+        grid[index] = 0;
+        if count >= limit {
+            break;
+        }
+    }
+    count
+}
+
+fn solve(grid: &Grid) -> Option<Grid> {
+    let mut solution = None;
+    count_solutions(&mut grid.clone(), 1, &mut solution);
+    solution
+}
+
+fn has_unique_solution(grid: &Grid) -> bool {
+    count_solutions(&mut grid.clone(), 2, &mut None) == 1
+}
+
+// remplit une grille complete en essayant les chiffres dans un ordre aleatoire
+fn fill_grid(grid: &mut Grid) -> bool {
+    let Some(index) = grid.iter().position(|&cell| cell == 0) else {
+        return true;
+    };
+
+    let mut digits: Vec<u8> = (1..=9).collect();
+    digits.shuffle(&mut rand::rng());
+    for digit in digits {
+        if can_place(grid, index, digit) {
+            grid[index] = digit;
+            if fill_grid(grid) {
+                return true;
+            }
+            grid[index] = 0;
+        }
+    }
+    false
+}
+
+// retire des chiffres tant que la solution reste unique
+fn generate(difficulty: Difficulty) -> Grid {
+    let mut grid = [0; 81];
+    fill_grid(&mut grid);
+
+    let mut cells: Vec<usize> = (0..81).collect();
+    cells.shuffle(&mut rand::rng());
+    let mut clues = 81;
+    for index in cells {
+        if clues <= difficulty.clues() {
+            break;
+        }
+        let digit = grid[index];
+        grid[index] = 0;
+        if has_unique_solution(&grid) {
+            clues -= 1;
+        } else {
+            grid[index] = digit;
+        }
+    }
+    grid
+}
+
+// format standard : 81 caracteres, '0' ou '.' pour une case vide
+fn parse_puzzle(input: &str) -> Option<Grid> {
+    let digits: Vec<u8> = input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '.' => Some(0),
+            c => c.to_digit(10).map(|digit| digit as u8),
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    let grid: Grid = digits.try_into().ok()?;
+
+    // les chiffres donnes ne doivent pas deja se contredire
+    let consistent = (0..81).all(|index| grid[index] == 0 || can_place(&grid, index, grid[index]));
+    consistent.then_some(grid)
+}
+
+fn parse_cell(coordinate: &str) -> Option<usize> {
+    let mut chars = coordinate.chars();
+    let row = u8::try_from(chars.next()?).ok()?.checked_sub(b'A')? as usize;
+    let col = chars.as_str().parse::<usize>().ok()?.checked_sub(1)?;
+    if row >= 9 || col >= 9 {
+        return None;
+    }
+    Some(row * 9 + col)
+}
+
+fn cell_name(index: usize) -> String {
+    format!("{}{}", (b'A' + (index / 9) as u8) as char, index % 9 + 1)
+}
+
+// "B5 7" place, "B5 0" ou "x B5" efface, "n B5 1 2" bascule des notes
+fn parse_command(input: &str) -> Option<Command> {
+    let input = input.trim().to_uppercase();
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    match parts.as_slice() {
+        ["HINT"] => Some(Command::Hint),
+        ["QUIT"] => Some(Command::Quit),
+        ["X", cell] => Some(Command::Clear(parse_cell(cell)?)),
+        ["N", cell, digits @ ..] if !digits.is_empty() => {
+            let digits = digits
+                .iter()
+                .map(|digit| digit.parse::<u8>().ok().filter(|digit| (1..=9).contains(digit)))
+                .collect::<Option<Vec<u8>>>()?;
+            Some(Command::Note(parse_cell(cell)?, digits))
+        }
+        [cell, digit] => match digit.parse::<u8>().ok()? {
+            0 => Some(Command::Clear(parse_cell(cell)?)),
+            digit @ 1..=9 => Some(Command::Place(parse_cell(cell)?, digit)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+struct Puzzle {
+    givens: Grid,
+    grid: Grid,
+    solution: Grid,
+    notes: Vec<Vec<u8>>,
+    hints_used: u64,
+}
+
+impl Puzzle {
+    fn new(givens: Grid, solution: Grid) -> Self {
+        Puzzle {
+            givens,
+            grid: givens,
+            solution,
+            notes: vec![Vec::new(); 81],
+            hints_used: 0,
+        }
+    }
+
+    fn is_solved(&self) -> bool {
+        self.grid == self.solution
+    }
+
+    fn place(&mut self, index: usize, digit: u8) -> Result<(), String> {
+        if self.givens[index] != 0 {
+            return Err(format!("{} is part of the puzzle and cannot be changed.", cell_name(index)));
+        }
+        if !can_place(&self.grid, index, digit) {
+            return Err(format!("{} conflicts with its row, column or box.", digit));
+        }
+        self.grid[index] = digit;
+        self.notes[index].clear();
+        // le chiffre place n'est plus un candidat pour ses voisins
+        for other in 0..81 {
+            if self.grid[other] == 0 && !can_place(&self.grid, other, digit) {
+                self.notes[other].retain(|&note| note != digit);
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, index: usize) -> Result<(), String> {
+        if self.givens[index] != 0 {
+            return Err(format!("{} is part of the puzzle and cannot be changed.", cell_name(index)));
+        }
+        self.grid[index] = 0;
+        Ok(())
+    }
+
+    fn toggle_notes(&mut self, index: usize, digits: &[u8]) -> Result<(), String> {
+        if self.grid[index] != 0 {
+            return Err(format!("{} is already filled.", cell_name(index)));
+        }
+        for &digit in digits {
+            match self.notes[index].iter().position(|&note| note == digit) {
+                Some(position) => {
+                    self.notes[index].remove(position);
+                }
+                None => self.notes[index].push(digit),
+            }
+        }
+        self.notes[index].sort();
+        Ok(())
+    }
+
+    // signale d'abord une erreur du joueur, sinon remplit une case vide au hasard
+    fn hint(&mut self) -> String {
+        self.hints_used += 1;
+        if let Some(index) = (0..81).find(|&i| self.grid[i] != 0 && self.grid[i] != self.solution[i]) {
+            return format!("{} is wrong, it should be {}.", cell_name(index), self.solution[index]);
+        }
+
+        let empty: Vec<usize> = (0..81).filter(|&i| self.grid[i] == 0).collect();
+        let index = empty[rand::rng().random_range(0..empty.len())];
+        self.grid[index] = self.solution[index];
+        self.notes[index].clear();
+        format!("{} is {}.", cell_name(index), self.solution[index])
+    }
+
+    fn render(&self) -> String {
+        let mut lines = vec!["    1 2 3   4 5 6   7 8 9".to_string()];
+        for row in 0..9 {
+            if row % 3 == 0 {
+                lines.push("  +-------+-------+-------+".to_string());
+            }
+            let mut line = format!("{} |", (b'A' + row as u8) as char);
+            for col in 0..9 {
+                let index = row * 9 + col;
+                let cell = match self.grid[index] {
+                    0 => ".".to_string(),
+                    digit if self.givens[index] != 0 => digit.to_string(),
+                    // les chiffres du joueur sont en couleur
+                    digit => format!("{}", digit.to_string().cyan()),
+                };
+                line.push_str(&format!(" {}", cell));
+                if col % 3 == 2 {
+                    line.push_str(" |");
+                }
+            }
+            lines.push(line);
+        }
+        lines.push("  +-------+-------+-------+".to_string());
+
+        let notes: Vec<String> = (0..81)
+            .filter(|&i| self.grid[i] == 0 && !self.notes[i].is_empty())
+            .map(|i| {
+                let digits: Vec<String> = self.notes[i].iter().map(|digit| digit.to_string()).collect();
+                format!("{}: {}", cell_name(i), digits.join(" "))
+            })
+            .collect();
+        if !notes.is_empty() {
+            lines.push(format!("Notes: {}", notes.join(", ")));
+        }
+        lines.join("\n")
+    }
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Sudoku Game !\n");
+
+    println!("Select a difficulty:");
+    println!("1. Easy");
+    println!("2. Medium");
+    println!("3. Hard");
+    println!("4. Import a puzzle (81 characters, '0' or '.' for empty cells)");
+    let difficulty = match utils::get_valid_choice() {
+        1 => Some(Difficulty::Easy),
+        2 => Some(Difficulty::Medium),
+        3 => Some(Difficulty::Hard),
+        4 => None,
+        _ => {
+            println!("Invalid choice. Exiting game.");
+            return Ok(());
+        }
+    };
+
+    loop {
+        let givens = match difficulty {
+            Some(difficulty) => {
+                println!("Generating a puzzle...");
+                generate(difficulty)
+            }
+            None => match import_puzzle() {
+                Some(givens) => givens,
+                None => return Ok(()),
+            },
+        };
+        let Some(solution) = solve(&givens) else {
+            println!("This puzzle has no solution.");
+            return Ok(());
+        };
+
+        display_rules();
+        let mut puzzle = Puzzle::new(givens, solution);
+        let start_time = Instant::now();
+        let mut solved = false;
+
+        while !solved {
+            println!("\n{}\n", puzzle.render());
+            println!("Time: {}s  Hints used: {}", start_time.elapsed().as_secs(), puzzle.hints_used);
+
+            let result = match parse_command(&utils::get_valid_input("Your move:")) {
+                Some(Command::Place(index, digit)) => puzzle.place(index, digit),
+                Some(Command::Clear(index)) => puzzle.clear(index),
+                Some(Command::Note(index, digits)) => puzzle.toggle_notes(index, &digits),
+                Some(Command::Hint) => {
+                    println!("{}", puzzle.hint());
+                    Ok(())
+                }
+                Some(Command::Quit) => break,
+                None => Err("Invalid command.".to_string()),
+            };
+            if let Err(message) = result {
+                println!("{}", message);
+            }
+            solved = puzzle.is_solved();
+        }
+
+        if solved {
+            println!("\n{}\n", puzzle.render());
+            let seconds = start_time.elapsed().as_secs() + puzzle.hints_used * HINT_PENALTY_SECONDS;
+            println!("Congrats! You solved the puzzle in {} seconds (hint penalties included).", seconds);
+            match difficulty {
+                Some(difficulty) => match DB::add_game_score(difficulty.table_name(), user_id, &username, seconds as i32) {
+                    Ok(_) => {},
+                    Err(e) => println!("Failed to save score: {}", e),
+                },
+                None => println!("Imported puzzles are not ranked."),
+            }
+        } else {
+            println!("The solution was:\n\n{}", Puzzle::new(solution, solution).render());
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn import_puzzle() -> Option<Grid> {
+    loop {
+        let input = utils::get_valid_input("Paste the puzzle (or 'q' to go back):");
+        if input.trim().eq_ignore_ascii_case("q") {
+            return None;
+        }
+        match parse_puzzle(&input) {
+            Some(grid) if has_unique_solution(&grid) => return Some(grid),
+            Some(_) => println!("This puzzle does not have a unique solution."),
+            None => println!("Invalid puzzle. Enter 81 digits, with '0' or '.' for empty cells."),
+        }
+    }
+}
+
+fn display_rules() {
+    println!("\nFill the grid so that every row, column and box contains the digits 1 to 9.");
+    println!("Commands:");
+    println!("  B5 7       place 7 in B5 (B5 0 clears it)");
+    println!("  x B5       clear B5");
+    println!("  n B5 1 2   toggle the notes 1 and 2 in B5");
+    println!("  hint       get a hint (+{}s)", HINT_PENALTY_SECONDS);
+    println!("  quit       give up and show the solution");
+}
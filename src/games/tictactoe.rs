@@ -0,0 +1,327 @@
+use std::error::Error;
+use rand::Rng;
+
+use crate::db::{DbManager as DB, Outcome, INITIAL_RATING};
+use crate::User;
+use crate::utils;
+
+pub const GAME_NAME: &str = "tictactoe";
+// facteur K du classement Elo
+const RATING_K: f64 = 32.0;
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2], [3, 4, 5], [6, 7, 8],
+    [0, 3, 6], [1, 4, 7], [2, 5, 8],
+    [0, 4, 8], [2, 4, 6],
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    X,
+    O,
+}
+
+impl Mark {
+    fn other(&self) -> Mark {
+        match self {
+            Mark::X => Mark::O,
+            Mark::O => Mark::X,
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            Mark::X => 'X',
+            Mark::O => 'O',
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AiLevel {
+    Random,
+    Heuristic,
+    Perfect,
+}
+
+impl AiLevel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AiLevel::Random => "ai_random",
+            AiLevel::Heuristic => "ai_heuristic",
+            AiLevel::Perfect => "ai_perfect",
+        }
+    }
+
+    // rating fixe de chaque IA, sert d'adversaire dans le calcul Elo
+    fn rating(&self) -> i32 {
+        match self {
+            AiLevel::Random => 800,
+            AiLevel::Heuristic => 1200,
+            AiLevel::Perfect => 1600,
+        }
+    }
+
+    fn choose_move(&self, board: &Board, mark: Mark) -> usize {
+        match self {
+            AiLevel::Random => board.random_move(),
+            AiLevel::Heuristic => board.heuristic_move(mark),
+            AiLevel::Perfect => board.best_move(mark),
+        }
+    }
+}
+
+enum Opponent {
+    Ai(AiLevel),
+    HotSeat,
+}
+
+#[derive(PartialEq)]
+enum GameState {
+    InProgress,
+    Won(Mark),
+    Draw,
+}
+
+#[derive(Clone)]
+struct Board {
+    cells: [Option<Mark>; 9],
+}
+
+impl Board {
+    fn new() -> Self {
+        Board { cells: [None; 9] }
+    }
+
+    fn free_cells(&self) -> Vec<usize> {
+        (0..9).filter(|&i| self.cells[i].is_none()).collect()
+    }
+
+    fn state(&self) -> GameState {
+        for line in LINES {
+            match line.map(|cell| self.cells[cell]) {
+                [Some(a), Some(b), Some(c)] if a == b && b == c => return GameState::Won(a),
+                _ => {}
+            }
+        }
+        if self.free_cells().is_empty() {
+            GameState::Draw
+        } else {
+            GameState::InProgress
+        }
+    }
+
+    fn render(&self) -> String {
+        let rows: Vec<String> = self
+            .cells
+            .chunks(3)
+            .enumerate()
+            .map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(col, cell)| match cell {
+                        Some(mark) => format!(" {} ", mark.as_char()),
+                        // les cases libres affichent leur numero
+                        None => format!(" {} ", row * 3 + col + 1),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("|")
+            })
+            .collect();
+        rows.join("\n---+---+---\n")
+    }
+
+    fn random_move(&self) -> usize {
+        let free = self.free_cells();
+        free[rand::rng().random_range(0..free.len())]
+    }
+
+    // case qui complete une ligne pour `mark`, s'il y en a une
+    fn winning_move(&self, mark: Mark) -> Option<usize> {
+        self.free_cells().into_iter().find(|&cell| {
+            let mut board = self.clone();
+            board.cells[cell] = Some(mark);
+            board.state() == GameState::Won(mark)
+        })
+    }
+
+    // gagner, sinon bloquer, sinon centre, coin puis bord
+    fn heuristic_move(&self, mark: Mark) -> usize {
+        if let Some(cell) = self.winning_move(mark) {
+            return cell;
+        }
+        if let Some(cell) = self.winning_move(mark.other()) {
+            return cell;
+        }
+        [4, 0, 2, 6, 8, 1, 3, 5, 7]
+            .into_iter()
+            .find(|&cell| self.cells[cell].is_none())
+            .unwrap_or_else(|| self.random_move())
+    }
+
+    fn best_move(&self, mark: Mark) -> usize {
+        self.free_cells()
+            .into_iter()
+            .max_by_key(|&cell| {
+                let mut board = self.clone();
+                board.cells[cell] = Some(mark);
+                -board.minimax(mark.other(), mark, 1)
+            })
+            .unwrap_or_else(|| self.random_move())
+    }
+
+    // score du point de vue de `to_play` : une victoire rapide vaut plus qu'une lente
+    fn minimax(&self, to_play: Mark, last_played: Mark, depth: i32) -> i32 {
+        match self.state() {
+            GameState::Won(mark) if mark == last_played => return -(10 - depth),
+            GameState::Won(_) => return 10 - depth,
+            GameState::Draw => return 0,
+            GameState::InProgress => {}
+        }
+
+        self.free_cells()
+            .into_iter()
+            .map(|cell| {
+                let mut board = self.clone();
+                board.cells[cell] = Some(to_play);
+                -board.minimax(to_play.other(), to_play, depth + 1)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Tic-tac-toe Game !\n");
+
+    println!("Select your opponent:");
+    println!("1. Computer - easy (random)");
+    println!("2. Computer - medium (heuristic)");
+    println!("3. Computer - hard (perfect)");
+    println!("4. Two players (hot seat)");
+    let opponent = match utils::get_valid_choice() {
+        1 => Opponent::Ai(AiLevel::Random),
+        2 => Opponent::Ai(AiLevel::Heuristic),
+        3 => Opponent::Ai(AiLevel::Perfect),
+        4 => Opponent::HotSeat,
+        _ => {
+            println!("Invalid choice. Exiting game.");
+            return Ok(());
+        }
+    };
+
+    // le joueur qui commence alterne a chaque partie
+    let mut user_starts = true;
+    loop {
+        match &opponent {
+            Opponent::Ai(level) => {
+                let outcome = play_against_ai(*level, user_starts);
+                update_rating(user_id, &username, *level, outcome);
+            }
+            Opponent::HotSeat => play_hot_seat(&username, user_starts),
+        }
+        user_starts = !user_starts;
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_move(board: &Board, player: &str, mark: Mark) -> usize {
+    loop {
+        println!("{} ({}), choose a cell (1-9):", player, mark.as_char());
+        let cell = utils::get_valid_choice() as usize;
+        if (1..=9).contains(&cell) && board.cells[cell - 1].is_none() {
+            return cell - 1;
+        }
+        println!("This cell is not available.");
+    }
+}
+
+fn play_against_ai(level: AiLevel, user_starts: bool) -> Outcome {
+    let mut board = Board::new();
+    // X commence toujours
+    let user_mark = if user_starts { Mark::X } else { Mark::O };
+    let mut to_play = Mark::X;
+
+    while board.state() == GameState::InProgress {
+        let cell = if to_play == user_mark {
+            println!("\n{}\n", board.render());
+            read_move(&board, "You", user_mark)
+        } else {
+            let cell = level.choose_move(&board, to_play);
+            println!("The computer plays {}.", cell + 1);
+            cell
+        };
+        board.cells[cell] = Some(to_play);
+        to_play = to_play.other();
+    }
+
+    println!("\n{}\n", board.render());
+    match board.state() {
+        GameState::Won(mark) if mark == user_mark => {
+            println!("Congrats! You won!");
+            Outcome::Win
+        }
+        GameState::Won(_) => {
+            println!("The computer won!");
+            Outcome::Loss
+        }
+        _ => {
+            println!("It's a draw!");
+            Outcome::Draw
+        }
+    }
+}
+
+fn play_hot_seat(username: &str, user_starts: bool) {
+    let mut board = Board::new();
+    let (x_player, o_player) = if user_starts { (username, "Guest") } else { ("Guest", username) };
+    let mut to_play = Mark::X;
+
+    while board.state() == GameState::InProgress {
+        println!("\n{}\n", board.render());
+        let player = if to_play == Mark::X { x_player } else { o_player };
+        let cell = read_move(&board, player, to_play);
+        board.cells[cell] = Some(to_play);
+        to_play = to_play.other();
+    }
+
+    println!("\n{}\n", board.render());
+    match board.state() {
+        GameState::Won(Mark::X) => println!("{} won!", x_player),
+        GameState::Won(Mark::O) => println!("{} won!", o_player),
+        _ => println!("It's a draw!"),
+    }
+    println!("Hot seat games are not rated.");
+}
+
+fn update_rating(user_id: i64, username: &str, level: AiLevel, outcome: Outcome) {
+    // sans rating lisible, on ne sauve rien plutot que de repartir de 1000
+    let rating = match DB::get_user_rating(GAME_NAME, user_id) {
+        Ok(rating) => rating.unwrap_or(INITIAL_RATING),
+        Err(e) => {
+            println!("Failed to load your rating, this game is not rated: {}", e);
+            return;
+        }
+    };
+    let expected = 1.0 / (1.0 + 10f64.powf((level.rating() - rating) as f64 / 400.0));
+    let new_rating = rating + (RATING_K * (outcome.points() - expected)).round() as i32;
+
+    println!("Your rating: {} -> {} ({:+})", rating, new_rating, new_rating - rating);
+    match DB::add_game_outcome(GAME_NAME, user_id, username, level.as_str(), outcome, new_rating) {
+        Ok(_) => {},
+        Err(e) => println!("Failed to save result: {}", e),
+    }
+}
@@ -0,0 +1,180 @@
+use std::error::Error;
+use rand::Rng;
+
+use crate::db::DbManager as DB;
+use crate::User;
+use crate::input;
+use crate::utils;
+use crate::words;
+
+pub const GAME_NAME: &str = "typing";
+const WORD_COUNT: usize = 20;
+// un "mot" standard fait 5 caracteres, espace compris
+const CHARS_PER_WORD: f64 = 5.0;
+
+#[derive(Clone, Copy)]
+enum Language {
+    English,
+    French,
+}
+
+impl Language {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(Language::English),
+            2 => Some(Language::French),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::French => "french",
+        }
+    }
+
+    fn words(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => words::ENGLISH_WORDS,
+            Language::French => words::FRENCH_WORDS,
+        }
+    }
+}
+
+struct TestResult {
+    wpm: f64,
+    raw_wpm: f64,
+    accuracy: f64,
+    // (mot attendu, mot tape) pour chaque mot faux ou manquant
+    errors: Vec<(String, String)>,
+}
+
+fn evaluate(targets: &[&str], typed: &str, minutes: f64) -> TestResult {
+    let typed_words: Vec<&str> = typed.split_whitespace().collect();
+    let mut correct_chars = 0;
+    let mut matching_chars = 0;
+    let mut typed_chars = 0;
+    let mut errors = Vec::new();
+
+    for (i, target) in targets.iter().enumerate() {
+        let typed_word = typed_words.get(i).copied().unwrap_or("");
+        typed_chars += typed_word.chars().count();
+        matching_chars += target
+            .chars()
+            .zip(typed_word.chars())
+            .filter(|(expected, actual)| expected == actual)
+            .count();
+
+        if typed_word == *target {
+            // le mot et l'espace qui le suit
+            correct_chars += target.chars().count() + 1;
+        } else {
+            errors.push((target.to_string(), typed_word.to_string()));
+        }
+    }
+    for extra in typed_words.iter().skip(targets.len()) {
+        typed_chars += extra.chars().count();
+        errors.push((String::new(), extra.to_string()));
+    }
+
+    let spaces = typed_words.len().saturating_sub(1);
+    TestResult {
+        wpm: correct_chars as f64 / CHARS_PER_WORD / minutes,
+        raw_wpm: (typed_chars + spaces) as f64 / CHARS_PER_WORD / minutes,
+        accuracy: if typed_chars == 0 {
+            0.0
+        } else {
+            matching_chars as f64 * 100.0 / typed_chars as f64
+        },
+        errors,
+    }
+}
+
+pub fn play_game(user: &User) -> Result<(), Box<dyn Error>> {
+    let user_id = user.id;
+    let username = user.username.clone();
+
+    utils::clear_terminal();
+    println!("Welcome on the Typing Test !\n");
+
+    println!("Select a language:");
+    println!("1. English");
+    println!("2. French");
+    let language = match Language::from_choice(utils::get_valid_choice()) {
+        Some(language) => language,
+        None => {
+            println!("Invalid choice. Exiting game.");
+            return Ok(());
+        }
+    };
+
+    loop {
+        let mut rng = rand::rng();
+        let word_list = language.words();
+        let targets: Vec<&str> = (0..WORD_COUNT)
+            .map(|_| word_list[rng.random_range(0..word_list.len())])
+            .collect();
+
+        utils::clear_terminal();
+        println!("Type the following words, separated by spaces, then press 'Enter'.");
+        println!("The timer starts with your first key press.\n");
+        println!("{}\n", targets.join(" "));
+
+        let (typed, first_key_at) = input::read_line_timed()?;
+        let minutes = match first_key_at {
+            Some(start) => start.elapsed().as_secs_f64() / 60.0,
+            None => 0.0,
+        };
+        if minutes <= 0.0 || typed.trim().is_empty() {
+            println!("Nothing was typed.");
+        } else {
+            let result = evaluate(&targets, &typed, minutes);
+            display_result(&result);
+
+            let previous_best = DB::get_typing_personal_best(user_id, language.as_str()).unwrap_or(None);
+            match DB::add_typing_result(user_id, language.as_str(), result.wpm, result.raw_wpm, result.accuracy) {
+                Ok(_) => {},
+                Err(e) => println!("Failed to save result: {}", e),
+            }
+            match DB::add_game_score(GAME_NAME, user_id, &username, result.wpm.round() as i32) {
+                Ok(_) => {},
+                Err(e) => println!("Failed to save score: {}", e),
+            }
+            match previous_best {
+                Some(best) if result.wpm <= best => println!("Personal best ({}): {:.1} WPM", language.as_str(), best),
+                _ => println!("New personal best in {}!", language.as_str()),
+            }
+        }
+
+        match utils::play_again() {
+            true => utils::clear_terminal(),
+            false => {
+                utils::clear_terminal();
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn display_result(result: &TestResult) {
+    println!("\nWPM: {:.1}", result.wpm);
+    println!("Raw WPM: {:.1}", result.raw_wpm);
+    println!("Accuracy: {:.1}%", result.accuracy);
+
+    if result.errors.is_empty() {
+        println!("No mistakes, well done!");
+        return;
+    }
+
+    println!("\nMistakes:");
+    for (expected, typed) in &result.errors {
+        match (expected.is_empty(), typed.is_empty()) {
+            (true, _) => println!("  extra word '{}'", typed),
+            (_, true) => println!("  '{}' missing", expected),
+            _ => println!("  '{}' typed as '{}'", expected, typed),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, queue, terminal};
+use std::io::{self, Write};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+static PANIC_HOOK: Once = Once::new();
+const REDRAW_INTERVAL_MS: u64 = 100;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Key {
+    Char(char),
+    Left,
+    Right,
+    Up,
+    Down,
+    Enter,
+    Backspace,
+    Esc,
+    Other,
+}
+
+// active le mode brut tant que le garde est en vie, le terminal est
+// restaure a la destruction du garde, en cas de panique ou sur Ctrl+C
+pub struct RawMode;
+
+impl RawMode {
+    pub fn enable() -> io::Result<RawMode> {
+        install_panic_hook();
+        terminal::enable_raw_mode()?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = terminal::disable_raw_mode();
+            previous_hook(info);
+        }));
+    });
+}
+
+// attend une touche (mode brut requis) et renvoie l'instant ou elle a ete recue
+pub fn read_key() -> io::Result<(Key, Instant)> {
+    loop {
+        if let Event::Key(key_event) = event::read()? {
+            let pressed_at = Instant::now();
+            // Windows envoie aussi les relachements de touche
+            if key_event.kind == KeyEventKind::Press {
+                return Ok((translate(key_event), pressed_at));
+            }
+        }
+    }
+}
+
+// attend une touche au plus `timeout`, renvoie None si rien n'a ete presse
+pub fn poll_key(timeout: Duration) -> io::Result<Option<(Key, Instant)>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if !event::poll(remaining)? {
+            return Ok(None);
+        }
+        if let Event::Key(key_event) = event::read()? {
+            let pressed_at = Instant::now();
+            if key_event.kind == KeyEventKind::Press {
+                return Ok(Some((translate(key_event), pressed_at)));
+            }
+        }
+    }
+}
+
+// vide les touches deja en attente (ex: tapees avant le debut d'un essai)
+pub fn discard_pending_keys() -> io::Result<()> {
+    while event::poll(Duration::ZERO)? {
+        event::read()?;
+    }
+    Ok(())
+}
+
+// lit une ligne sans bloquer l'affichage : l'invite est redessinee a chaque
+// tick avec le temps restant, renvoie None si la deadline est depassee
+pub fn read_line_until(deadline: Instant, prompt: impl Fn(Duration) -> String) -> io::Result<Option<String>> {
+    let _raw_mode = RawMode::enable()?;
+    let mut stdout = io::stdout();
+    let mut buffer = String::new();
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        queue!(stdout, cursor::MoveToColumn(0), terminal::Clear(terminal::ClearType::CurrentLine))?;
+        write!(stdout, "{}{}", prompt(remaining), buffer)?;
+        stdout.flush()?;
+
+        if remaining.is_zero() {
+            write!(stdout, "\r\n")?;
+            return Ok(None);
+        }
+
+        match poll_key(remaining.min(Duration::from_millis(REDRAW_INTERVAL_MS)))? {
+            Some((Key::Enter, _)) => {
+                write!(stdout, "\r\n")?;
+                return Ok(Some(buffer));
+            }
+            Some((Key::Backspace, _)) => {
+                buffer.pop();
+            }
+            Some((Key::Char(c), _)) => buffer.push(c),
+            _ => {}
+        }
+    }
+}
+
+// lit une ligne en mode brut et renvoie aussi l'instant de la premiere touche
+pub fn read_line_timed() -> io::Result<(String, Option<Instant>)> {
+    let _raw_mode = RawMode::enable()?;
+    let mut stdout = io::stdout();
+    let mut buffer = String::new();
+    let mut first_key_at = None;
+
+    loop {
+        let (key, pressed_at) = read_key()?;
+        first_key_at.get_or_insert(pressed_at);
+        match key {
+            Key::Enter => {
+                write!(stdout, "\r\n")?;
+                stdout.flush()?;
+                return Ok((buffer, first_key_at));
+            }
+            Key::Backspace if buffer.pop().is_some() => {
+                write!(stdout, "\u{8} \u{8}")?;
+            }
+            Key::Char(c) => {
+                buffer.push(c);
+                write!(stdout, "{}", c)?;
+            }
+            _ => {}
+        }
+        stdout.flush()?;
+    }
+}
+
+fn translate(key_event: KeyEvent) -> Key {
+    if key_event.modifiers.contains(KeyModifiers::CONTROL) && key_event.code == KeyCode::Char('c') {
+        let _ = terminal::disable_raw_mode();
+        println!();
+        std::process::exit(130);
+    }
+
+    match key_event.code {
+        KeyCode::Char(c) => Key::Char(c.to_ascii_lowercase()),
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Esc => Key::Esc,
+        _ => Key::Other,
+    }
+}
@@ -1,16 +1,36 @@
 mod db;
+mod input;
 mod utils;
+mod words;
 mod games {
+    pub mod game2048;
     pub mod guessing;
+    pub mod hangman;
+    pub mod mastermind;
+    pub mod minesweeper;
     pub mod motus;
+    pub mod motus_solver;
     pub mod reflex;
+    pub mod reflex_stats;
+    pub mod simon;
+    pub mod sudoku;
+    pub mod tictactoe;
+    pub mod typing;
 }
 
 use db::DbManager as DB;
 use utils::*;
+use games::game2048;
 use games::guessing;
+use games::hangman;
+use games::mastermind;
+use games::minesweeper;
 use games::motus;
 use games::reflex;
+use games::simon;
+use games::sudoku;
+use games::tictactoe;
+use games::typing;
 
 struct User{
     id: i64,
@@ -29,7 +49,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>>{
         println!("1. Play a game");
         println!("2. Your best scores");
         println!("3. Leaderboards");
-        println!("4. Quit");
+        println!("4. Settings");
+        println!("5. Quit");
 
         let choice = get_valid_choice();
         utils::clear_terminal();
@@ -39,7 +60,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>>{
                 println!("1. Guessing Game");
                 println!("2. Motus");
                 println!("3. Reflex");
-                println!("4. Quit");
+                println!("4. Hangman");
+                println!("5. Mastermind");
+                println!("6. Typing Test");
+                println!("7. Simon");
+                println!("8. Tic-tac-toe");
+                println!("9. Minesweeper");
+                println!("10. 2048");
+                println!("11. Sudoku");
+                println!("12. Quit");
 
                 let game_choice = get_valid_choice();
                 match game_choice {
@@ -59,6 +88,46 @@ fn main() -> Result<(), Box<dyn std::error::Error>>{
                         }
                     },
                     4 => {
+                        if let Err(e) = hangman::play_game(&user_session) {
+                            println!("Error playing Hangman: {}", e);
+                        }
+                    },
+                    5 => {
+                        if let Err(e) = mastermind::play_game(&user_session) {
+                            println!("Error playing Mastermind: {}", e);
+                        }
+                    },
+                    6 => {
+                        if let Err(e) = typing::play_game(&user_session) {
+                            println!("Error playing Typing Test: {}", e);
+                        }
+                    },
+                    7 => {
+                        if let Err(e) = simon::play_game(&user_session) {
+                            println!("Error playing Simon: {}", e);
+                        }
+                    },
+                    8 => {
+                        if let Err(e) = tictactoe::play_game(&user_session) {
+                            println!("Error playing Tic-tac-toe: {}", e);
+                        }
+                    },
+                    9 => {
+                        if let Err(e) = minesweeper::play_game(&user_session) {
+                            println!("Error playing Minesweeper: {}", e);
+                        }
+                    },
+                    10 => {
+                        if let Err(e) = game2048::play_game(&user_session) {
+                            println!("Error playing 2048: {}", e);
+                        }
+                    },
+                    11 => {
+                        if let Err(e) = sudoku::play_game(&user_session) {
+                            println!("Error playing Sudoku: {}", e);
+                        }
+                    },
+                    12 => {
                         println!("Thanks for playing, {}! See you soon!", user_session.username);
                         return Ok(());
                     },
@@ -75,7 +144,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>>{
                     println!("Error displaying leaderboards: {}", e);
                 }
             },
-            4 => break,
+            4 => {
+                if let Err(e) = settings_menu(&user_session) {
+                    println!("Error saving settings: {}", e);
+                }
+            },
+            5 => break,
             _ => println!("Invalid choice, please try again."),
         }
     }
@@ -100,10 +174,19 @@ fn get_or_create_user(username: &str) -> Result<User, Box<dyn std::error::Error>
     })
 }
 
+fn settings_menu(user: &User) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Settings\n");
+    println!("1. Reflex key bindings");
+    println!("2. Back");
+
+    match get_valid_choice() {
+        1 => reflex::choose_key_bindings(user),
+        _ => Ok(()),
+    }
+}
+
 fn display_all_leaderboards() -> Result<(), Box<dyn std::error::Error>> {
-    let games = vec!["guessing", "motus", "reflex"];
-    
-    for game in games {
+    for game in db::SCORE_TABLES {
         println!("Leaderboard for {}:", game);
         match DB::get_leaderboard(game) {
             Ok(leaderboard) => {
@@ -118,6 +201,22 @@ fn display_all_leaderboards() -> Result<(), Box<dyn std::error::Error>> {
             Err(e) => println!("  Could not load leaderboard: {}", e),
         }
     }
+
+    for game in db::RATING_TABLES {
+        println!("Ratings for {}:", game);
+        match DB::get_rating_leaderboard(game) {
+            Ok(leaderboard) => {
+                if leaderboard.is_empty() {
+                    println!("  No games recorded yet.");
+                } else {
+                    for (i, (name, rating)) in leaderboard.iter().enumerate() {
+                        println!("  {}. {}: {}", i + 1, name, rating);
+                    }
+                }
+            },
+            Err(e) => println!("  Could not load ratings: {}", e),
+        }
+    }
     
     Ok(())
 }
@@ -139,6 +238,16 @@ fn get_user_best_scores(user: &User) -> Result<(), Box<dyn std::error::Error>> {
         },
         Err(e) => println!("Error retrieving scores: {}", e),
     }
+
+    for game in db::RATING_TABLES {
+        match (DB::get_user_rating(game, user_id), DB::get_user_outcomes(game, user_id)) {
+            (Ok(Some(rating)), Ok((wins, draws, losses))) => {
+                println!("\t{}: rating {} ({} W / {} D / {} L)", game, rating, wins, draws, losses)
+            },
+            (Ok(None), _) => println!("\t{}: No games yet", game),
+            (Err(e), _) | (_, Err(e)) => println!("Error retrieving rating: {}", e),
+        }
+    }
     
     println!();
     Ok(())
@@ -1,14 +1,12 @@
 pub fn clear_terminal() {
-    // Pour Windows
-    if cfg!(target_os = "windows") {
-        let _ = std::process::Command::new("cmd")
-            .args(["/c", "cls"])
-            .status();
-    } else {
-        // Pour Unix 
-        let _ = std::process::Command::new("clear")
-            .status();
-    }
+    use crossterm::{cursor, execute, terminal};
+    // sequences ANSI plutot qu'un processus `clear` / `cls`, bien plus rapide
+    let _ = execute!(
+        std::io::stdout(),
+        terminal::Clear(terminal::ClearType::Purge),
+        terminal::Clear(terminal::ClearType::All),
+        cursor::MoveTo(0, 0)
+    );
 }
 
 pub fn get_valid_choice() -> u32 {
@@ -44,8 +42,12 @@ pub fn get_valid_input(prompt: &str) -> String {
 }
 
 pub fn play_again() -> bool {
+    confirm("Do you want to play again? (y/n)")
+}
+
+pub fn confirm(prompt: &str) -> bool {
     loop {
-        let input = get_valid_input("Do you want to play again? (y/n)");
+        let input = get_valid_input(prompt);
         match input.to_lowercase().as_str() {
             "y" => return true,
             "n" => return false,
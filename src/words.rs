@@ -0,0 +1,480 @@
+use once_cell::sync::Lazy;
+use reqwest::blocking;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::utils;
+
+pub const RANDOM_WORD_API_URL: &str = "https://random-word-api.herokuapp.com";
+pub const TROUVE_MOT_API_URL: &str = "https://trouve-mot.fr";
+
+pub const ENGLISH_WORDS: &[&str] = &[
+    "apple", "beach", "brain", "bread", "brush", "chair", "chest", "chord", "click", "clock",
+    "cloud", "dance", "diary", "drink", "earth", "feast", "field", "fruit", "glass", "grape",
+    "green", "ghost", "heart", "horse", "house", "juice", "light", "lemon", "melon", "money",
+    "music", "night", "ocean", "party", "piano", "pilot", "plane", "plant", "plate", "queen",
+    "radio", "river", "robot", "salad", "sheep", "shirt", "shoes", "smile", "snake", "space",
+    "spoon", "storm", "table", "tiger", "toast", "train", "water", "whale", "world", "zebra",
+];
+
+pub const FRENCH_WORDS: &[&str] = &[
+    "arbre", "avion", "balle", "bateau", "beurre", "blague", "bougie", "cadeau", "carte", "chaise",
+    "chapeau", "cheval", "chien", "citron", "classe", "cochon", "crayon", "fleur", "fraise", "fromage",
+    "gateau", "girafe", "guitare", "herbe", "jardin", "lapin", "livre", "maison", "mouton", "nuage",
+    "orange", "oiseau", "pomme", "poisson", "porte", "prune", "radis", "renard", "robot", "sable",
+    "salade", "singe", "soleil", "sucre", "table", "tigre", "train", "tulipe", "vache", "valise",
+];
+
+// listes de secours par categorie, si l'API et le cache sont indisponibles
+const FR_AGRICULTURE_WORDS: &[&str] = &[
+    "ble", "champ", "ferme", "grange", "tracteur", "recolte", "semis", "charrue", "verger", "vigne",
+    "moisson", "silo", "foin", "paille", "etable", "engrais", "labour", "prairie", "betail", "serre",
+];
+
+const FR_ARMEE_WORDS: &[&str] = &[
+    "soldat", "caserne", "fusil", "canon", "general", "colonel", "sergent", "bataillon", "regiment", "char",
+    "uniforme", "tranchee", "casque", "grenade", "escadron", "brigade", "manoeuvre", "garnison", "bunker", "caporal",
+];
+
+const FR_ANIMAUX_WORDS: &[&str] = &[
+    "chien", "chat", "cheval", "vache", "mouton", "lapin", "renard", "loup", "ours", "tigre",
+    "lion", "girafe", "zebre", "singe", "aigle", "hibou", "dauphin", "baleine", "tortue", "serpent",
+];
+
+const FR_INDUSTRIE_WORDS: &[&str] = &[
+    "usine", "atelier", "machine", "acier", "fonderie", "chaine", "ouvrier", "moteur", "turbine", "forge",
+    "entrepot", "outil", "presse", "robot", "mine", "raffinerie", "chantier", "soudure", "metal", "production",
+];
+
+const FR_NOURRITURE_WORDS: &[&str] = &[
+    "pain", "fromage", "beurre", "pomme", "poire", "carotte", "salade", "tomate", "gateau", "chocolat",
+    "soupe", "viande", "poisson", "riz", "pates", "yaourt", "confiture", "crepe", "tarte", "omelette",
+];
+
+const CACHE_DIR: &str = "data/cache";
+const CUSTOM_WORDS_PATH: &str = "data/words/custom.txt";
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_RETRIES: u32 = 3;
+const MAX_RETRIES: u32 = 5;
+const RETRY_BASE_DELAY_MS: u64 = 500;
+const MAX_RETRY_DELAY_MS: u64 = 8_000;
+
+// client HTTP partage, configurable par variables d'environnement :
+// HUB_HTTP_TIMEOUT (secondes), HUB_HTTP_RETRIES et HUB_HTTP_PROXY.
+// Les variables HTTP_PROXY / HTTPS_PROXY standards sont aussi respectees.
+static HTTP_CLIENT: Lazy<Result<blocking::Client, String>> = Lazy::new(|| {
+    let timeout = env_number("HUB_HTTP_TIMEOUT").unwrap_or(DEFAULT_TIMEOUT_SECS);
+    let mut builder = blocking::Client::builder()
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .timeout(Duration::from_secs(timeout));
+
+    if let Ok(proxy_url) = std::env::var("HUB_HTTP_PROXY") {
+        let proxy = reqwest::Proxy::all(&proxy_url)
+            .map_err(|e| format!("Invalid proxy {}: {}", proxy_url, e))?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder.build().map_err(|e| e.to_string())
+});
+
+fn env_number(name: &str) -> Option<u64> {
+    std::env::var(name).ok()?.trim().parse().ok()
+}
+
+pub trait WordSource {
+    fn fetch_words(&self) -> Result<Vec<String>, Box<dyn Error>>;
+}
+
+// client HTTP et nombre d'essais utilises par une source en ligne
+#[derive(Clone)]
+pub struct HttpSettings {
+    client: blocking::Client,
+    retries: u32,
+}
+
+impl HttpSettings {
+    pub fn new(client: blocking::Client, retries: u32) -> Self {
+        HttpSettings {
+            client,
+            retries: retries.clamp(1, MAX_RETRIES),
+        }
+    }
+
+    // le client partage et le nombre d'essais de HUB_HTTP_RETRIES
+    fn shared() -> Result<Self, Box<dyn Error>> {
+        let client = HTTP_CLIENT.as_ref().map_err(|e| e.clone())?;
+        let retries = env_number("HUB_HTTP_RETRIES").unwrap_or(DEFAULT_RETRIES as u64).min(MAX_RETRIES as u64) as u32;
+        Ok(HttpSettings::new(client.clone(), retries))
+    }
+
+    fn or_shared(settings: &Option<HttpSettings>) -> Result<Self, Box<dyn Error>> {
+        match settings {
+            Some(settings) => Ok(settings.clone()),
+            None => HttpSettings::shared(),
+        }
+    }
+}
+
+fn get_json<T: DeserializeOwned>(http: &HttpSettings, url: &str) -> Result<T, Box<dyn Error>> {
+    let client = &http.client;
+    let retries = http.retries;
+
+    let mut attempt = 1;
+    loop {
+        println!("Fetching words (attempt {}/{})...", attempt, retries);
+        match try_get_json(client, url) {
+            Ok(value) => return Ok(value),
+            Err(e) if e.retryable && attempt < retries => {
+                let delay = 2u64
+                    .checked_pow(attempt - 1)
+                    .map_or(MAX_RETRY_DELAY_MS, |factor| RETRY_BASE_DELAY_MS.saturating_mul(factor))
+                    .min(MAX_RETRY_DELAY_MS);
+                println!("Request failed: {}. Retrying in {} ms...", e.error, delay);
+                thread::sleep(Duration::from_millis(delay));
+                attempt += 1;
+            }
+            Err(e) => return Err(e.error),
+        }
+    }
+}
+
+// seuls les timeouts, les erreurs de connexion et les erreurs 5xx valent
+// un nouvel essai : un 404 ou un JSON invalide ne se corrigeront pas seuls
+struct RequestError {
+    error: Box<dyn Error>,
+    retryable: bool,
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(error: reqwest::Error) -> Self {
+        let retryable = error.is_timeout() || error.is_connect();
+        RequestError { error: error.into(), retryable }
+    }
+}
+
+fn try_get_json<T: DeserializeOwned>(client: &blocking::Client, url: &str) -> Result<T, RequestError> {
+    let response = client.get(url).send()?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(RequestError {
+            error: format!("Failed to fetch words: HTTP {}", status).into(),
+            retryable: status.is_server_error(),
+        });
+    }
+    Ok(response.json()?)
+}
+
+// recupere les mots depuis la source et les met en cache ; si la source
+// est injoignable on se rabat sur le cache, puis sur la liste embarquee
+pub fn fetch_with_fallback(source: &dyn WordSource, cache_name: &str, bundled: &'static [&'static str]) -> Vec<String> {
+    let cache_path = format!("{}/{}.txt", CACHE_DIR, cache_name);
+
+    match source.fetch_words() {
+        Ok(words) if !words.is_empty() => {
+            if let Err(e) = fs::create_dir_all(CACHE_DIR).and_then(|_| fs::write(&cache_path, words.join("\n"))) {
+                println!("Could not cache words: {}", e);
+            }
+            return words;
+        }
+        Ok(_) => println!("The word API returned no words."),
+        Err(e) => println!("Could not fetch words: {}", e),
+    }
+
+    match FileSource::new(&cache_path).fetch_words() {
+        Ok(words) if !words.is_empty() => {
+            println!("Using {} cached words instead.", words.len());
+            words
+        }
+        _ => {
+            println!("Using the bundled word list instead.");
+            bundled.iter().map(|word| word.to_string()).collect()
+        }
+    }
+}
+
+pub struct RandomWordApi {
+    http: Option<HttpSettings>,
+    base_url: String,
+    count: u32,
+    length: u32,
+}
+
+impl RandomWordApi {
+    pub fn new(count: u32, length: u32) -> Self {
+        Self::with_base_url(None, RANDOM_WORD_API_URL, count, length)
+    }
+
+    // `http` a None utilise le client partage
+    pub fn with_base_url(http: Option<HttpSettings>, base_url: &str, count: u32, length: u32) -> Self {
+        RandomWordApi {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            count,
+            length,
+        }
+    }
+}
+
+impl WordSource for RandomWordApi {
+    fn fetch_words(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let url = format!("{}/word?number={}&length={}", self.base_url, self.count, self.length);
+        let words: Vec<String> = get_json(&HttpSettings::or_shared(&self.http)?, &url)?;
+        Ok(words.iter().map(|word| utils::remove_accents(word)).collect())
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ApiWord {
+    name: String,
+    #[serde(default)] // Rend le champ optionnel
+    _categorie: Option<String>,
+}
+
+pub struct TrouveMot {
+    http: Option<HttpSettings>,
+    base_url: String,
+    category: u32,
+    count: u32,
+}
+
+impl TrouveMot {
+    pub fn new(category: u32, count: u32) -> Self {
+        Self::with_base_url(None, TROUVE_MOT_API_URL, category, count)
+    }
+
+    pub fn with_base_url(http: Option<HttpSettings>, base_url: &str, category: u32, count: u32) -> Self {
+        TrouveMot {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            category,
+            count,
+        }
+    }
+}
+
+impl WordSource for TrouveMot {
+    fn fetch_words(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let url = format!("{}/api/categorie/{}/{}", self.base_url, self.category, self.count);
+        let api_words: Vec<ApiWord> = get_json(&HttpSettings::or_shared(&self.http)?, &url)?;
+        Ok(api_words
+            .into_iter()
+            .map(|word| utils::remove_accents(&word.name))
+            .collect())
+    }
+}
+
+// un mot par ligne, les lignes vides et les commentaires (#) sont ignores
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSource { path: path.into() }
+    }
+}
+
+impl WordSource for FileSource {
+    fn fetch_words(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?;
+        Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(utils::remove_accents)
+            .collect())
+    }
+}
+
+pub struct EmbeddedSource {
+    words: &'static [&'static str],
+}
+
+impl EmbeddedSource {
+    pub fn new(words: &'static [&'static str]) -> Self {
+        EmbeddedSource { words }
+    }
+}
+
+impl WordSource for EmbeddedSource {
+    fn fetch_words(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(self.words.iter().map(|word| word.to_string()).collect())
+    }
+}
+
+pub enum Theme {
+    English,
+    FrAgriculture,
+    FrArmee,
+    FrAnimaux,
+    FrIndustrie,
+    FrNourriture,
+    Offline,
+    Custom,
+}
+
+impl Theme {
+    fn from_choice(choice: u32) -> Option<Self> {
+        match choice {
+            1 => Some(Theme::English),
+            2 => Some(Theme::FrAgriculture),
+            3 => Some(Theme::FrArmee),
+            4 => Some(Theme::FrAnimaux),
+            5 => Some(Theme::FrIndustrie),
+            6 => Some(Theme::FrNourriture),
+            7 => Some(Theme::Offline),
+            8 => Some(Theme::Custom),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::English => "english words",
+            Theme::FrAgriculture => "french agriculture",
+            Theme::FrArmee => "french army",
+            Theme::FrAnimaux => "french animals",
+            Theme::FrIndustrie => "french industry",
+            Theme::FrNourriture => "french food",
+            Theme::Offline => "offline english words",
+            Theme::Custom => "custom word list",
+        }
+    }
+
+    fn word_source(&self) -> Box<dyn WordSource> {
+        match self {
+            Theme::English => Box::new(RandomWordApi::new(50, 5)),
+            Theme::FrAgriculture => Box::new(TrouveMot::new(11, 50)),
+            Theme::FrArmee => Box::new(TrouveMot::new(26, 50)),
+            Theme::FrAnimaux => Box::new(TrouveMot::new(19, 50)),
+            Theme::FrIndustrie => Box::new(TrouveMot::new(9, 50)),
+            Theme::FrNourriture => Box::new(TrouveMot::new(5, 50)),
+            Theme::Offline => Box::new(EmbeddedSource::new(ENGLISH_WORDS)),
+            Theme::Custom => Box::new(FileSource::new(CUSTOM_WORDS_PATH)),
+        }
+    }
+
+    fn cache_name(&self) -> &'static str {
+        match self {
+            Theme::English => "english",
+            Theme::FrAgriculture => "fr_agriculture",
+            Theme::FrArmee => "fr_armee",
+            Theme::FrAnimaux => "fr_animaux",
+            Theme::FrIndustrie => "fr_industrie",
+            Theme::FrNourriture => "fr_nourriture",
+            Theme::Offline => "offline",
+            Theme::Custom => "custom",
+        }
+    }
+
+    fn bundled_words(&self) -> &'static [&'static str] {
+        match self {
+            Theme::English | Theme::Offline | Theme::Custom => ENGLISH_WORDS,
+            Theme::FrAgriculture => FR_AGRICULTURE_WORDS,
+            Theme::FrArmee => FR_ARMEE_WORDS,
+            Theme::FrAnimaux => FR_ANIMAUX_WORDS,
+            Theme::FrIndustrie => FR_INDUSTRIE_WORDS,
+            Theme::FrNourriture => FR_NOURRITURE_WORDS,
+        }
+    }
+
+    pub fn load_words(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        match self {
+            Theme::Offline | Theme::Custom => self.word_source().fetch_words(),
+            _ => Ok(fetch_with_fallback(&*self.word_source(), self.cache_name(), self.bundled_words())),
+        }
+    }
+}
+
+pub fn select_theme() -> Option<Theme> {
+    println!("Select a theme: ");
+    println!("1. English");
+    println!("2. French Agriculture");
+    println!("3. French Army");
+    println!("4. French Animals");
+    println!("5. French Industry");
+    println!("6. French Food");
+    println!("7. Offline (bundled English words)");
+    println!("8. Custom list ({})", CUSTOM_WORDS_PATH);
+
+    let choice = utils::get_valid_choice();
+    Theme::from_choice(choice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    // serveur local qui repond une seule fois avec `body` et renvoie la ligne de requete recue
+    fn stub_server(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8_lossy(&request).lines().next().unwrap_or_default().to_string()
+        });
+        (base_url, handle)
+    }
+
+    // sans proxy ni nouvel essai, quel que soit l'environnement de la machine
+    fn stub_http() -> HttpSettings {
+        let client = blocking::Client::builder()
+            .no_proxy()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        HttpSettings::new(client, 1)
+    }
+
+    #[test]
+    fn random_word_api_parses_and_strips_accents() {
+        let (base_url, server) = stub_server(r#"["Apple","café","NAÏVE"]"#);
+        let words = RandomWordApi::with_base_url(Some(stub_http()), &base_url, 3, 5).fetch_words().unwrap();
+
+        assert_eq!(words, vec!["apple", "cafe", "naive"]);
+        assert_eq!(server.join().unwrap(), "GET /word?number=3&length=5 HTTP/1.1");
+    }
+
+    #[test]
+    fn trouve_mot_parses_and_strips_accents() {
+        let (base_url, server) = stub_server(r#"[{"name":"Blé","categorie":"11"},{"name":"récolte"}]"#);
+        let words = TrouveMot::with_base_url(Some(stub_http()), &base_url, 11, 2).fetch_words().unwrap();
+
+        assert_eq!(words, vec!["ble", "recolte"]);
+        assert_eq!(server.join().unwrap(), "GET /api/categorie/11/2 HTTP/1.1");
+    }
+
+    #[test]
+    fn file_source_skips_blank_and_comment_lines() {
+        let path = std::env::temp_dir().join(format!("hub_gaming_words_{}.txt", std::process::id()));
+        fs::write(&path, "# my words\nApple\n\n   \n  Poème  \n#skipped\n").unwrap();
+        let words = FileSource::new(&path).fetch_words();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(words.unwrap(), vec!["apple", "poeme"]);
+    }
+}